@@ -1,5 +1,30 @@
 pub const GAME_TIMEOUT_SECONDS: i64 = 1800; // 30 mins
-pub const ENTRY_AMOUNT_USDC: u64 = 1000; // 1000 USDC (with 6 decimals)
-pub const USDC_DECIMALS: u32 = 6; // USDC on Solana has 6 decimal places: 1 USDC = 1,000,000 base units
 pub const MAX_JOIN_PRICE_MOVEMENT: f64 = 0.01; // 1% max change for joining
-pub const WIN_PRICE_THRESHOLD: f64 = 0.05; // 5% movement for win
+pub const WIN_PRICE_THRESHOLD_BPS: u16 = 500; // 5% movement for win, unless the game sets its own
+pub const MAX_WIN_PRICE_THRESHOLD_BPS: u16 = 5000; // 50% max movement a game can require to win
+pub const MAX_WIN_CONDITION_ROUNDS: usize = 32; // Most oracle rounds a sustained win can be proven with
+pub const MAX_ROUND_PROOF_AGE_SECONDS: i64 = 60; // Oldest the last round proving a sustained win can be
+pub const MAX_PRICE_SOURCES: usize = 5; // Most price sources a market can combine
+pub const START_GAME_WINDOW_SECONDS: i64 = 300; // 5 mins after a scheduled start to capture the initial price
+pub const ORACLE_OUTAGE_GRACE_SECONDS: i64 = 300; // 5 mins without a new round before games can be refunded
+pub const MAX_SESSION_DURATION_SECONDS: i64 = 604800; // 7 days
+pub const INITIAL_RATING: u32 = 1200; // Rating assigned to new player profiles
+pub const RATING_K_FACTOR: f64 = 32.0; // Maximum rating change from a single game
+pub const BPS_DENOMINATOR: u16 = 10000; // 100% in basis points
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1000; // 10% max fee on a game's payout
+pub const MAX_SEASON_WINNERS: usize = 10; // Most ranks a season can pay out
+pub const SEASON_POINTS_WIN: u64 = 3; // Season points for winning a game
+pub const MAX_TOURNAMENT_PLAYERS: usize = 16; // Largest bracket a tournament can run
+pub const TOURNAMENT_ROUND_TIMEOUT_SECONDS: i64 = 3600; // 1 hour to create a match game each round
+pub const MAX_HOUSE_POOL_MARKETS: usize = 16; // Most markets the house pool can be exposed to at once
+pub const HOUSE_POOL_LOCKED_SHARES: u64 = 1000; // Shares locked by the first deposit into a house pool
+
+// Game actions a session key can be authorized for
+pub const SESSION_ACTION_CREATE_GAME: u8 = 1 << 0;
+pub const SESSION_ACTION_JOIN_GAME: u8 = 1 << 1;
+pub const SESSION_ACTION_CLOSE_GAME: u8 = 1 << 2;
+pub const SESSION_ACTION_DRAW_GAME: u8 = 1 << 3;
+pub const SESSION_ACTIONS_ALL: u8 = SESSION_ACTION_CREATE_GAME
+    | SESSION_ACTION_JOIN_GAME
+    | SESSION_ACTION_CLOSE_GAME
+    | SESSION_ACTION_DRAW_GAME;
@@ -23,3 +23,51 @@ pub enum GameStatus {
     /// Game was cancelled before another player joined
     Cancelled,
 }
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TimeoutPolicy {
+    /// Both players get their stake back
+    Refund,
+
+    /// The side the price moved towards takes the whole pot
+    Directional,
+
+    /// The pot is split by how far the price moved towards the win threshold
+    Proportional,
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WinCondition {
+    /// The first price past the threshold wins
+    Instant,
+
+    /// The threshold must hold on this many consecutive oracle rounds
+    ConsecutiveRounds { rounds: u8 },
+
+    /// The time-weighted average price over this many seconds must pass the threshold
+    TimeWeighted { window_seconds: u32 },
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PricePolicy {
+    /// The first source with a fresh price is used
+    Fallback,
+
+    /// The median of the fresh sources is used, provided at least this many are fresh
+    Median { min_sources: u8 },
+}
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TournamentStatus {
+    /// Tournament is open for players to register
+    Registration,
+
+    /// Bracket has been drawn and matches are being played
+    InProgress,
+
+    /// A champion has been crowned and prizes can be claimed
+    Complete,
+
+    /// Tournament was called off and players can reclaim their entry fees
+    Cancelled,
+}
@@ -20,7 +20,7 @@ pub enum CustomError {
     #[msg("Invalid token account")]
     InvalidTokenAccount,
 
-    #[msg("Token mint must be USDC")]
+    #[msg("Token mint is not an accepted stake mint")]
     InvalidTokenMint,
 
     #[msg("Invalid price value received from oracle")]
@@ -61,4 +61,175 @@ pub enum CustomError {
 
     #[msg("Cannot join your own game")]
     CannotJoinOwnGame,
+
+    #[msg("Position cannot be transferred to a player already in the game")]
+    InvalidPositionRecipient,
+
+    #[msg("Position token does not belong to this game")]
+    InvalidPositionToken,
+
+    #[msg("Only the admin can perform this action")]
+    NotAdmin,
+
+    #[msg("Entry amount must be greater than zero")]
+    InvalidEntryAmount,
+
+    #[msg("Invalid session key expiry or permissions")]
+    InvalidSession,
+
+    #[msg("Session key has expired")]
+    SessionExpired,
+
+    #[msg("Session key is not allowed to perform this action")]
+    SessionActionNotAllowed,
+
+    #[msg("Session key stake limit exceeded")]
+    SessionStakeLimitExceeded,
+
+    #[msg("Rent refunds must go to the account that paid the rent")]
+    IncorrectRentPayer,
+
+    #[msg("Fees must be paid to the configured treasury")]
+    InvalidTreasury,
+
+    #[msg("Fee or payout share exceeds the allowed basis points")]
+    InvalidFeeBps,
+
+    #[msg("Invalid season schedule or payout curve")]
+    InvalidSeason,
+
+    #[msg("Season is not active or does not match the game")]
+    SeasonNotActive,
+
+    #[msg("The stake mint's current season must be passed")]
+    SeasonRequired,
+
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+
+    #[msg("Player did not place in the season's paid ranks")]
+    NotASeasonWinner,
+
+    #[msg("Prize has already been claimed")]
+    PrizeAlreadyClaimed,
+
+    #[msg("Referrer is invalid for this player")]
+    InvalidReferrer,
+
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+
+    #[msg("This game is reserved for another player")]
+    NotReservedChallenger,
+
+    #[msg("Invalid tournament settings")]
+    InvalidTournament,
+
+    #[msg("Tournament is not open for registration")]
+    RegistrationClosed,
+
+    #[msg("Tournament is full or the player is already registered")]
+    CannotRegister,
+
+    #[msg("Tournament cannot be started yet")]
+    TournamentNotReady,
+
+    #[msg("Tournament is not in progress")]
+    TournamentNotInProgress,
+
+    #[msg("Tournament has not finished yet")]
+    TournamentNotComplete,
+
+    #[msg("Tournament cannot be cancelled")]
+    TournamentNotCancellable,
+
+    #[msg("Tournament has not been cancelled")]
+    TournamentNotCancelled,
+
+    #[msg("Player is not in this tournament match")]
+    NotTournamentPlayer,
+
+    #[msg("Game does not belong to this tournament match")]
+    InvalidTournamentGame,
+
+    #[msg("Tournament match has not been decided yet")]
+    MatchNotDecided,
+
+    #[msg("Player did not place in the tournament's paid ranks")]
+    NotATournamentWinner,
+
+    #[msg("Insufficient balance")]
+    InsufficientBalance,
+
+    #[msg("Games funded from internal balances settle into each holder's balance")]
+    PlayerBalanceRequired,
+
+    #[msg("Escrow account does not match the game's vault")]
+    InvalidEscrow,
+
+    #[msg("Escrowed stakes exceed the vault balance")]
+    EscrowInvariantViolated,
+
+    #[msg("Joining would exceed the house pool's exposure limits")]
+    HouseExposureLimitExceeded,
+
+    #[msg("Not enough free liquidity in the house pool")]
+    InsufficientPoolLiquidity,
+
+    #[msg("Invalid liquidity amount")]
+    InvalidLiquidityAmount,
+
+    #[msg("House pool does not match the game")]
+    InvalidHousePool,
+
+    #[msg("The house pool can only join games whose price is anchored at join")]
+    HouseJoinRequiresAnchoredPrice,
+
+    #[msg("Games do not take opposite sides of the same bet")]
+    IncompatibleGames,
+
+    #[msg("Tournament games cannot be matched")]
+    CannotMatchTournamentGame,
+
+    #[msg("Start time must be in the future")]
+    InvalidStartTime,
+
+    #[msg("Game has not captured its start price yet")]
+    GameNotStarted,
+
+    #[msg("Game is not waiting for a scheduled start")]
+    GameNotScheduled,
+
+    #[msg("Scheduled start time has not been reached")]
+    StartTimeNotReached,
+
+    #[msg("Scheduled start window has passed without the game starting")]
+    StartWindowMissed,
+
+    #[msg("Game's initial price differs from the expected price")]
+    UnexpectedInitialPrice,
+
+    #[msg("Game has been refreshed since the expected refresh nonce")]
+    UnexpectedRefreshNonce,
+
+    #[msg("Price has moved against the challenger by more than they accept")]
+    ExcessiveAdverseDrift,
+
+    #[msg("Win threshold must be above 1% and at most 50%")]
+    InvalidWinThreshold,
+
+    #[msg("Invalid win condition")]
+    InvalidWinCondition,
+
+    #[msg("Oracle rounds do not prove the price the game is settled at")]
+    InvalidRoundProof,
+
+    #[msg("Invalid price source")]
+    InvalidPriceSource,
+
+    #[msg("No price source is fresh enough to use")]
+    PriceUnavailable,
+
+    #[msg("Price feed has not been stale for the outage grace period")]
+    OracleNotStale,
 }
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 
-use super::{GameStatus, PricePrediction};
+use super::{GameStatus, PricePolicy, PricePrediction, TimeoutPolicy, WinCondition};
 
 #[event]
 pub struct PriceFetched {
     pub description: String,
     pub price: f64,
+    pub round_timestamp: i64,
     pub timestamp: i64,
 }
 
@@ -25,7 +26,14 @@ pub struct GameCreated {
     pub initiator: Pubkey,
     pub prediction: PricePrediction,
     pub initial_price: f64,
+    pub stake_mint: Pubkey,
     pub entry_amount: u64,
+    pub start_at: Option<i64>,
+    pub anchor_price_at_join: bool,
+    pub timeout_policy: TimeoutPolicy,
+    pub increase_threshold_bps: u16,
+    pub decrease_threshold_bps: u16,
+    pub win_condition: WinCondition,
     pub timestamp: i64,
 }
 
@@ -35,14 +43,274 @@ pub game_id: u64,
 pub status: GameStatus,
 pub challenger: Pubkey,
 pub challenger_prediction: PricePrediction,
+pub initial_price: f64,
+pub initiator_position_mint: Pubkey,
+pub challenger_position_mint: Pubkey,
 pub timestamp: i64,
 }
 
+#[event]
+pub struct PositionTransferred {
+    pub game_id: u64,
+    pub prediction: PricePrediction,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionRedeemed {
+    pub game_id: u64,
+    pub prediction: PricePrediction,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowedMintUpdated {
+    pub mint: Pubkey,
+    pub entry_amount: u64,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub owner: Pubkey,
+    pub session_signer: Pubkey,
+    pub stake_mint: Pubkey,
+    pub max_stake: u64,
+    pub expires_at: i64,
+    pub allowed_actions: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub owner: Pubkey,
+    pub session_signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub season_share_bps: u16,
+    pub referral_share_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeasonCreated {
+    pub season_id: u64,
+    pub stake_mint: Pubkey,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub payout_curve: Vec<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeasonPrizeClaimed {
+    pub season_id: u64,
+    pub stake_mint: Pubkey,
+    pub player: Pubkey,
+    pub rank: u8,
+    pub points: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub player: Pubkey,
+    pub referrer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referrer: Pubkey,
+    pub player: Pubkey,
+    pub stake_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament_id: u64,
+    pub stake_mint: Pubkey,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub registration_ends_at: i64,
+    pub payout_curve: Vec<u16>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentPlayerRegistered {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub seed: u8,
+    pub pot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentStarted {
+    pub tournament_id: u64,
+    pub players: u8,
+    pub pot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentMatchDecided {
+    pub tournament_id: u64,
+    pub round: u8,
+    pub match_index: u8,
+    pub game: Option<Pubkey>,
+    pub winner: Pubkey,
+    pub loser: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentCompleted {
+    pub tournament_id: u64,
+    pub champion: Pubkey,
+    pub pot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentPrizeClaimed {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub rank: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentCancelled {
+    pub tournament_id: u64,
+    pub players: u8,
+    pub pot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TournamentRefundClaimed {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceDeposited {
+    pub owner: Pubkey,
+    pub stake_mint: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceWithdrawn {
+    pub owner: Pubkey,
+    pub stake_mint: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HousePoolUpdated {
+    pub stake_mint: Pubkey,
+    pub max_market_exposure: u64,
+    pub max_direction_exposure: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceSourcesUpdated {
+    pub price_feed: Pubkey,
+    pub policy: PricePolicy,
+    pub source_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceSourcesClosed {
+    pub price_feed: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceAggregated {
+    pub price_feed: Pubkey,
+    pub price: f64,
+    pub sources_used: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub provider: Pubkey,
+    pub stake_mint: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityWithdrawn {
+    pub provider: Pubkey,
+    pub stake_mint: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GamesMatched {
+    pub game_id: u64,
+    pub initiator: Pubkey,
+    pub matched_game_id: u64,
+    pub matched_initiator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameStarted {
+    pub game_id: u64,
+    pub initiator: Pubkey,
+    pub initial_price: f64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameRefreshed {
+    pub game_id: u64,
+    pub initiator: Pubkey,
+    pub initial_price: f64,
+    pub refresh_nonce: u32,
+    pub created_at: i64,
+}
+
 #[event]
 pub struct GameClosed {
     pub game_id: u64,
     pub status: GameStatus,
     pub details: GameStatusDetails,
+    pub initiator_rating_delta: i32,
+    pub challenger_rating_delta: i32,
     pub timestamp: i64,
 }
 
@@ -54,6 +322,19 @@ pub enum GameStatusDetails {
         price_movement_percentage: f64,
         final_price: f64,
         total_payout: u64,
+        protocol_fee: u64,
+    },
+    Timeout {
+        timeout_policy: TimeoutPolicy,
+        final_price: f64,
+        initiator_payout: u64,
+        challenger_payout: u64,
+        protocol_fee: u64,
+    },
+    OracleOutage {
+        price_feed: Pubkey,
+        last_price: f64,
+        last_round_timestamp: i64,
     },
     None,
 }
@@ -1,21 +1,90 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::Instruction,
+        program::{get_return_data, invoke},
+        program_pack::Pack,
+    },
+    system_program,
+};
+use anchor_spl::{
+    token::spl_token,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_interface::{
+        self, spl_token_2022::instruction::AuthorityType, Burn, CloseAccount, InitializeAccount3,
+        Mint, MintTo, SetAuthority, SyncNative, TokenAccount, TransferChecked,
+    },
+};
 use chainlink_solana as chainlink;
 
-use crate::common::{PriceChanged, PriceFetched, USDC_DECIMALS};
+use crate::{
+    common::{PriceAggregated, PriceChanged, PriceFetched},
+    state::{
+        AllowedMint, Config, GameState, PlayerBalance, PriceSource, PriceSources, Season,
+        SessionKey, SharedEscrow,
+    },
+};
 
-use super::CustomError;
+use super::{
+    CustomError, BPS_DENOMINATOR, MAX_WIN_CONDITION_ROUNDS, ORACLE_OUTAGE_GRACE_SECONDS,
+    RATING_K_FACTOR,
+};
+
+// Chainlink store query instruction, as built by the client library
+const CHAINLINK_QUERY_DISCRIMINATOR: [u8; 8] = [0x27, 0xfb, 0x82, 0x9f, 0x2e, 0x88, 0xa4, 0xa9];
+const CHAINLINK_QUERY_DECIMALS: u8 = 1;
+const CHAINLINK_QUERY_ROUND_DATA: u8 = 3;
+const CHAINLINK_QUERY_LATEST_ROUND_DATA: u8 = 4;
+
+// Pyth receiver program and the discriminator of the price update accounts it posts
+const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+// How thoroughly a Pyth price update's signatures were verified when it was posted
+#[derive(AnchorDeserialize, PartialEq)]
+enum PythVerificationLevel {
+    Partial { _num_signatures: u8 },
+    Full,
+}
+
+// A Pyth price update account, following its discriminator
+#[derive(AnchorDeserialize)]
+struct PythPriceUpdate {
+    _write_authority: Pubkey,
+    verification_level: PythVerificationLevel,
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+}
+
+// An oracle round, as returned by the Chainlink store
+#[derive(AnchorDeserialize)]
+struct ChainlinkRound {
+    round_id: u32,
+    _slot: u64,
+    timestamp: u32,
+    answer: i128,
+}
 
 // Format chainlink price response as a floating point number
 pub fn format_price(value: i128, decimals: u8) -> f64 {
     (value as f64) * 10_f64.powi(-(decimals as i32))
 }
 
-// Function to get the current price from Chainlink
+// Function to get the current price from Chainlink, along with the timestamp of the round
+// it was reported in
 pub fn get_chainlink_price<'info>(
     chainlink_program: &AccountInfo<'info>,
     chainlink_feed: &AccountInfo<'info>,
     current_time: i64,
-) -> Result<f64> {
+) -> Result<(f64, i64)> {
     let round = chainlink::latest_round_data(
         chainlink_program.to_account_info(),
         chainlink_feed.to_account_info(),
@@ -32,14 +101,292 @@ pub fn get_chainlink_price<'info>(
     )?;
 
     let formatted_price = format_price(round.answer, decimals);
+    let round_timestamp = round.timestamp as i64;
 
     emit!(PriceFetched {
         description: description.clone(),
         price: formatted_price,
+        round_timestamp,
         timestamp: current_time,
     });
 
-    Ok(formatted_price)
+    Ok((formatted_price, round_timestamp))
+}
+
+// Queries the Chainlink store directly, as the client library only exposes some of its
+// queries and ties the program and feed accounts to the same lifetime. Only the Chainlink
+// store program can answer.
+fn query_chainlink<T: AnchorDeserialize>(
+    chainlink_program: &Pubkey,
+    chainlink_feed: &AccountInfo,
+    query: &[u8],
+) -> Result<T> {
+    require_keys_eq!(
+        *chainlink_program,
+        chainlink::ID,
+        CustomError::InvalidPriceFeed
+    );
+
+    let mut data = CHAINLINK_QUERY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(query);
+
+    let ix = Instruction {
+        program_id: *chainlink_program,
+        accounts: vec![AccountMeta::new_readonly(chainlink_feed.key(), false)],
+        data,
+    };
+
+    invoke(&ix, std::slice::from_ref(chainlink_feed))?;
+
+    let (program_id, data) = get_return_data().ok_or(error!(CustomError::InvalidPriceFeed))?;
+
+    require_keys_eq!(
+        program_id,
+        *chainlink_program,
+        CustomError::InvalidPriceFeed
+    );
+
+    T::deserialize(&mut &data[..]).map_err(|_| error!(CustomError::InvalidPriceFeed))
+}
+
+// Fetches a past round from Chainlink
+fn get_chainlink_round(
+    chainlink_program: &Pubkey,
+    chainlink_feed: &AccountInfo,
+    round_id: u32,
+) -> Result<ChainlinkRound> {
+    let mut query = vec![CHAINLINK_QUERY_ROUND_DATA];
+    query.extend_from_slice(&round_id.to_le_bytes());
+
+    query_chainlink(chainlink_program, chainlink_feed, &query)
+}
+
+// Reads a Chainlink price source, returning None if its latest round is stale
+fn read_chainlink_source(
+    chainlink_program: &Pubkey,
+    chainlink_feed: &AccountInfo,
+    max_staleness_seconds: u32,
+    current_time: i64,
+) -> Result<Option<f64>> {
+    let round: ChainlinkRound = query_chainlink(
+        chainlink_program,
+        chainlink_feed,
+        &[CHAINLINK_QUERY_LATEST_ROUND_DATA],
+    )?;
+
+    if current_time - round.timestamp as i64 > max_staleness_seconds as i64 || round.answer <= 0 {
+        return Ok(None);
+    }
+
+    let decimals: u8 = query_chainlink(
+        chainlink_program,
+        chainlink_feed,
+        &[CHAINLINK_QUERY_DECIMALS],
+    )?;
+
+    Ok(Some(format_price(round.answer, decimals)))
+}
+
+// Reads a Pyth price source, returning None if its confidence interval is too wide relative
+// to the price. Stale updates are rejected.
+fn read_pyth_source(
+    price_update: &AccountInfo,
+    feed_id: [u8; 32],
+    max_staleness_seconds: u32,
+    max_confidence_bps: u16,
+    current_time: i64,
+) -> Result<Option<f64>> {
+    require_keys_eq!(
+        *price_update.owner,
+        PYTH_RECEIVER_PROGRAM_ID,
+        CustomError::InvalidPriceSource
+    );
+
+    let data = price_update.try_borrow_data()?;
+
+    require!(
+        data.len() > 8 && data[..8] == PYTH_PRICE_UPDATE_DISCRIMINATOR,
+        CustomError::InvalidPriceSource
+    );
+
+    let update = PythPriceUpdate::deserialize(&mut &data[8..])
+        .map_err(|_| error!(CustomError::InvalidPriceSource))?;
+
+    // Only fully verified updates for the configured feed can be used
+    require!(
+        update.verification_level == PythVerificationLevel::Full
+            && update.feed_id == feed_id
+            && update.price > 0,
+        CustomError::InvalidPriceSource
+    );
+
+    // The caller picks which update to pass, so a stale one is rejected rather than
+    // letting them drop the source
+    require!(
+        current_time - update.publish_time <= max_staleness_seconds as i64,
+        CustomError::StalePriceFeed
+    );
+
+    if update.conf as u128 * BPS_DENOMINATOR as u128
+        > update.price as u128 * max_confidence_bps as u128
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(update.price as f64 * 10_f64.powi(update.exponent)))
+}
+
+// Reads the price sources configured for a market, if it has any
+pub fn load_price_sources(price_sources: &AccountInfo) -> Result<Option<PriceSources>> {
+    if price_sources.data_is_empty() {
+        return Ok(None);
+    }
+
+    let price_sources = PriceSources::try_deserialize(&mut &price_sources.try_borrow_data()?[..])?;
+
+    Ok(Some(price_sources))
+}
+
+// Gets the current price of a market. Markets without price sources use their Chainlink
+// feed alone, as long as its latest round is within the outage grace period. Otherwise each
+// source's account is taken from `source_accounts` in the order the sources are configured,
+// and the fresh prices are combined by the sources' policy.
+pub fn get_price<'info>(
+    chainlink_program: &AccountInfo<'info>,
+    chainlink_feed: &AccountInfo<'info>,
+    price_sources: Option<&PriceSources>,
+    source_accounts: &[AccountInfo],
+    current_time: i64,
+) -> Result<f64> {
+    let Some(price_sources) = price_sources else {
+        let (price, round_timestamp) =
+            get_chainlink_price(chainlink_program, chainlink_feed, current_time)?;
+        require!(
+            current_time - round_timestamp <= ORACLE_OUTAGE_GRACE_SECONDS,
+            CustomError::StalePriceFeed
+        );
+        return Ok(price);
+    };
+
+    let prices = read_price_sources(
+        chainlink_program.key,
+        price_sources,
+        source_accounts,
+        current_time,
+    )?;
+    let (price, sources_used) = price_sources.combine_prices(&prices)?;
+
+    emit!(PriceAggregated {
+        price_feed: price_sources.price_feed,
+        price,
+        sources_used,
+        timestamp: current_time,
+    });
+
+    Ok(price)
+}
+
+// Returns true if a market has no price source with a price fresh enough to fall back on
+pub fn are_price_sources_unavailable(
+    chainlink_program: &Pubkey,
+    price_sources: Option<&PriceSources>,
+    source_accounts: &[AccountInfo],
+    current_time: i64,
+) -> Result<bool> {
+    let Some(price_sources) = price_sources else {
+        return Ok(true);
+    };
+
+    let prices = read_price_sources(
+        chainlink_program,
+        price_sources,
+        source_accounts,
+        current_time,
+    )?;
+
+    Ok(price_sources.combine_prices(&prices).is_err())
+}
+
+// Reads each of a market's price sources from its account in `source_accounts`, in order.
+// Chainlink feeds too stale to use and Pyth updates too uncertain to use have no price.
+fn read_price_sources(
+    chainlink_program: &Pubkey,
+    price_sources: &PriceSources,
+    source_accounts: &[AccountInfo],
+    current_time: i64,
+) -> Result<Vec<Option<f64>>> {
+    require!(
+        source_accounts.len() >= price_sources.sources.len(),
+        CustomError::InvalidPriceSource
+    );
+
+    price_sources
+        .sources
+        .iter()
+        .zip(source_accounts)
+        .map(|(source, account)| match *source {
+            PriceSource::Chainlink {
+                feed,
+                max_staleness_seconds,
+            } => {
+                require_keys_eq!(account.key(), feed, CustomError::InvalidPriceSource);
+
+                read_chainlink_source(
+                    chainlink_program,
+                    account,
+                    max_staleness_seconds,
+                    current_time,
+                )
+            }
+            PriceSource::Pyth {
+                feed_id,
+                max_staleness_seconds,
+                max_confidence_bps,
+            } => read_pyth_source(
+                account,
+                feed_id,
+                max_staleness_seconds,
+                max_confidence_bps,
+                current_time,
+            ),
+        })
+        .collect()
+}
+
+// Fetches consecutive Chainlink rounds as (price, timestamp) pairs, oldest first
+pub fn get_chainlink_round_history<'info>(
+    chainlink_program: &AccountInfo<'info>,
+    chainlink_feed: &AccountInfo<'info>,
+    round_ids: &[u32],
+) -> Result<Vec<(f64, i64)>> {
+    require!(
+        !round_ids.is_empty() && round_ids.len() <= MAX_WIN_CONDITION_ROUNDS,
+        CustomError::InvalidRoundProof
+    );
+
+    require!(
+        round_ids
+            .windows(2)
+            .all(|pair| pair[0].checked_add(1) == Some(pair[1])),
+        CustomError::InvalidRoundProof
+    );
+
+    let decimals: u8 = query_chainlink(
+        chainlink_program.key,
+        chainlink_feed,
+        &[CHAINLINK_QUERY_DECIMALS],
+    )?;
+
+    round_ids
+        .iter()
+        .map(|&round_id| {
+            let round = get_chainlink_round(chainlink_program.key, chainlink_feed, round_id)?;
+
+            require!(round.round_id == round_id, CustomError::InvalidRoundProof);
+
+            Ok((format_price(round.answer, decimals), round.timestamp as i64))
+        })
+        .collect()
 }
 
 // Checks if price has moved by given percentage
@@ -78,10 +425,492 @@ pub fn has_price_moved_by_percentage(
     Ok((threshold_exceeded, direction, percentage_change))
 }
 
-// Converts a human-readable USDC amount to on-chain representation
-// Example: 1000 -> 1_000_000_000
-pub fn usdc_to_on_chain_amount(amount: u64) -> u64 {
-    let usdc_multiplier: u64 = 10u64.pow(USDC_DECIMALS);
+// Calculates the Elo rating change for a player given their score against the opponent
+// (1 for a win, 0.5 for a draw and 0 for a loss)
+pub fn get_rating_delta(rating: u32, opponent_rating: u32, score: f64) -> i32 {
+    let expected_score = 1.0 / (1.0 + 10f64.powf((opponent_rating as f64 - rating as f64) / 400.0));
+
+    (RATING_K_FACTOR * (score - expected_score)).round() as i32
+}
+
+// Calculates a basis point share of an amount, rounding down
+pub fn calculate_bps_share(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(error!(CustomError::Overflow))?
+        / BPS_DENOMINATOR as u128;
 
-    amount * usdc_multiplier
+    u64::try_from(share).map_err(|_| error!(CustomError::Overflow))
+}
+
+// Calculates the amount to send so that `net_amount` arrives after any Token-2022 transfer fee
+// Mints without a transfer fee extension return `net_amount` unchanged
+pub fn get_gross_transfer_amount(mint: &AccountInfo, net_amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(net_amount);
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    let Ok(transfer_fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(net_amount);
+    };
+
+    let fee = transfer_fee_config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .ok_or(error!(CustomError::Overflow))?;
+
+    net_amount
+        .checked_add(fee)
+        .ok_or(error!(CustomError::Overflow))
+}
+
+// Reads the latest season created for a stake mint from its allowed mint entry, if the
+// mint has one
+pub fn get_current_season(allowed_mint: &AccountInfo) -> Result<Option<Pubkey>> {
+    if allowed_mint.data_is_empty() {
+        return Ok(None);
+    }
+
+    let allowed_mint = AllowedMint::try_deserialize(&mut &allowed_mint.try_borrow_data()?[..])?;
+
+    Ok(allowed_mint.season)
+}
+
+// Returns the stake mint's current season if it is running. The season must be passed
+// whenever the mint has one, and must not be passed otherwise.
+pub fn get_running_season(
+    allowed_mint: &AccountInfo,
+    season: Option<&Account<Season>>,
+    current_time: i64,
+) -> Result<Option<Pubkey>> {
+    match get_current_season(allowed_mint)? {
+        Some(current_season) => {
+            let season = season.ok_or(error!(CustomError::SeasonRequired))?;
+
+            require!(season.key() == current_season, CustomError::SeasonNotActive);
+            Ok(season.is_active(current_time).then_some(current_season))
+        }
+        None => {
+            require!(season.is_none(), CustomError::SeasonNotActive);
+            Ok(None)
+        }
+    }
+}
+
+// Returns true if the mint is wrapped SOL, whose stakes are wrapped and unwrapped automatically
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+// Wraps lamports from the owner's wallet into their native token account
+pub fn wrap_sol<'info>(
+    owner: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let cpi_accounts = system_program::Transfer {
+        from: owner.clone(),
+        to: token_account.clone(),
+    };
+    system_program::transfer(
+        CpiContext::new(system_program.clone(), cpi_accounts),
+        lamports,
+    )?;
+
+    let cpi_accounts = SyncNative {
+        account: token_account.clone(),
+    };
+    token_interface::sync_native(CpiContext::new(token_program.clone(), cpi_accounts))
+}
+
+// Pays wrapped SOL out of a token account as lamports. The payouts are moved into a temporary
+// native token account, which is closed to the payer, who then forwards each payout to its
+// recipient. Recipients' own token accounts are left untouched. `authority` owns the source
+// account and signs with `signer`, or has signed the transaction itself
+#[allow(clippy::too_many_arguments)]
+pub fn unwrap_sol<'info>(
+    from: &AccountInfo<'info>,
+    stake_mint: &InterfaceAccount<'info, Mint>,
+    authority: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    sol_payout: &AccountInfo<'info>,
+    sol_payout_bump: u8,
+    payer: &AccountInfo<'info>,
+    payouts: &[(AccountInfo<'info>, u64)],
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let total_amount = payouts
+        .iter()
+        .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+        .ok_or(error!(CustomError::Overflow))?;
+
+    if total_amount == 0 {
+        return Ok(());
+    }
+
+    let payer_key = payer.key();
+    let sol_payout_seeds = &[
+        b"sol_payout".as_ref(),
+        payer_key.as_ref(),
+        &[sol_payout_bump],
+    ];
+    let sol_payout_signer = &[&sol_payout_seeds[..]];
+
+    // Create the temporary account, topping up any lamports already sent to its address
+    let space = spl_token::state::Account::LEN;
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = sol_payout.lamports();
+
+    if current_lamports == 0 {
+        let cpi_accounts = system_program::CreateAccount {
+            from: payer.clone(),
+            to: sol_payout.clone(),
+        };
+        system_program::create_account(
+            CpiContext::new_with_signer(system_program.clone(), cpi_accounts, sol_payout_signer),
+            rent_lamports,
+            space as u64,
+            token_program.key,
+        )?;
+    } else {
+        if current_lamports < rent_lamports {
+            let cpi_accounts = system_program::Transfer {
+                from: payer.clone(),
+                to: sol_payout.clone(),
+            };
+            system_program::transfer(
+                CpiContext::new(system_program.clone(), cpi_accounts),
+                rent_lamports - current_lamports,
+            )?;
+        }
+
+        let cpi_accounts = system_program::Allocate {
+            account_to_allocate: sol_payout.clone(),
+        };
+        system_program::allocate(
+            CpiContext::new_with_signer(system_program.clone(), cpi_accounts, sol_payout_signer),
+            space as u64,
+        )?;
+
+        let cpi_accounts = system_program::Assign {
+            account_to_assign: sol_payout.clone(),
+        };
+        system_program::assign(
+            CpiContext::new_with_signer(system_program.clone(), cpi_accounts, sol_payout_signer),
+            token_program.key,
+        )?;
+    }
+
+    let cpi_accounts = InitializeAccount3 {
+        account: sol_payout.clone(),
+        mint: stake_mint.to_account_info(),
+        authority: sol_payout.clone(),
+    };
+    token_interface::initialize_account3(CpiContext::new(token_program.clone(), cpi_accounts))?;
+
+    transfer_from_vault(
+        from,
+        sol_payout,
+        stake_mint,
+        authority,
+        token_program,
+        signer,
+        total_amount,
+    )?;
+
+    let cpi_accounts = CloseAccount {
+        account: sol_payout.clone(),
+        destination: payer.clone(),
+        authority: sol_payout.clone(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        cpi_accounts,
+        sol_payout_signer,
+    ))?;
+
+    for (recipient, amount) in payouts {
+        if *amount == 0 || recipient.key == payer.key {
+            continue;
+        }
+
+        let cpi_accounts = system_program::Transfer {
+            from: payer.clone(),
+            to: recipient.clone(),
+        };
+        system_program::transfer(
+            CpiContext::new(system_program.clone(), cpi_accounts),
+            *amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+// Checks the signer may act for the player, either as the player or through their session key
+// The session account's seeds already bind it to the player and the signer
+pub fn authorize_player(
+    authority: &Pubkey,
+    player: &Pubkey,
+    session: Option<&SessionKey>,
+    action: u8,
+    current_time: i64,
+) -> Result<()> {
+    match session {
+        Some(session) => session.validate_action(action, current_time),
+        None => {
+            require!(authority == player, CustomError::NotAuthorized);
+            Ok(())
+        }
+    }
+}
+
+// Moves a player's stake into the vault, covering any transfer fee so the vault receives
+// `amount` in full. Session keys spend through their delegation on the player's token account
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_stake<'info>(
+    player: &AccountInfo<'info>,
+    player_token_account: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    stake_mint: &InterfaceAccount<'info, Mint>,
+    session: Option<&mut Account<'info, SessionKey>>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let deposit_amount = get_gross_transfer_amount(&stake_mint.to_account_info(), amount)?;
+
+    let mut cpi_accounts = TransferChecked {
+        from: player_token_account.clone(),
+        to: vault.clone(),
+        mint: stake_mint.to_account_info(),
+        authority: player.clone(),
+    };
+
+    match session {
+        Some(session) => {
+            session.record_stake(stake_mint.key(), deposit_amount)?;
+
+            cpi_accounts.authority = session.to_account_info();
+            let seeds = session.signer_seeds();
+            let signer = &[&seeds[..]];
+            let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer);
+
+            token_interface::transfer_checked(cpi_ctx, deposit_amount, stake_mint.decimals)
+        }
+        None => {
+            // Wrap SOL stakes straight from the player's wallet
+            if is_native_mint(&stake_mint.key()) {
+                wrap_sol(
+                    player,
+                    player_token_account,
+                    system_program,
+                    token_program,
+                    deposit_amount,
+                )?;
+            }
+
+            let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+
+            token_interface::transfer_checked(cpi_ctx, deposit_amount, stake_mint.decimals)
+        }
+    }
+}
+
+// Stakes a player's entry amount from their internal balance. The tokens stay in the mint's
+// balance vault, which holds the stakes of games funded this way, so nothing is transferred.
+pub fn stake_from_balance<'info>(
+    balance: &mut Account<'info, PlayerBalance>,
+    stake_mint: Pubkey,
+    session: Option<&mut Account<'info, SessionKey>>,
+    amount: u64,
+) -> Result<()> {
+    if let Some(session) = session {
+        session.record_stake(stake_mint, amount)?;
+    }
+
+    balance.debit(amount)
+}
+
+// Credits a holder's share of a game held in the balance vault to their internal balance,
+// creating the balance if needed. The tokens stay in the balance vault.
+pub fn credit_balance(
+    balance: Option<&mut Account<PlayerBalance>>,
+    bump: Option<u8>,
+    holder: Pubkey,
+    stake_mint: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let (Some(balance), Some(bump)) = (balance, bump) else {
+        return err!(CustomError::PlayerBalanceRequired);
+    };
+
+    balance.initialize(holder, stake_mint, bump);
+    balance.credit(amount)
+}
+
+// Pays out of a program-owned token account, signed by the PDA that owns it
+// Zero amounts are skipped
+pub fn transfer_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    stake_mint: &InterfaceAccount<'info, Mint>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = TransferChecked {
+        from: vault.clone(),
+        to: to.clone(),
+        mint: stake_mint.to_account_info(),
+        authority: authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer);
+
+    token_interface::transfer_checked(cpi_ctx, amount, stake_mint.decimals)
+}
+
+// Returns the PDA that owns a game's vault along with its signer seeds: the config for games
+// holding their stakes in the mint's balance vault, the mint's shared escrow for games
+// holding them in its vault, or the game itself
+pub fn vault_authority_and_signer<'info>(
+    game_state: &Account<'info, GameState>,
+    shared_escrow: Option<&Account<'info, SharedEscrow>>,
+    config: &Account<'info, Config>,
+) -> Result<(AccountInfo<'info>, Vec<Vec<u8>>)> {
+    if game_state.balance_escrow {
+        let seeds = vec![b"config".to_vec(), vec![config.bump]];
+
+        return Ok((config.to_account_info(), seeds));
+    }
+
+    if !game_state.shared_escrow {
+        let seeds = vec![
+            b"game_state".to_vec(),
+            game_state.initiator.to_bytes().to_vec(),
+            game_state.game_id.to_le_bytes().to_vec(),
+            vec![game_state.bump],
+        ];
+
+        return Ok((game_state.to_account_info(), seeds));
+    }
+
+    let shared_escrow = shared_escrow.ok_or(error!(CustomError::InvalidEscrow))?;
+    let seeds = vec![
+        b"shared_escrow".to_vec(),
+        shared_escrow.stake_mint.to_bytes().to_vec(),
+        vec![shared_escrow.bump],
+    ];
+
+    Ok((shared_escrow.to_account_info(), seeds))
+}
+
+// Closes a game vault once it has been emptied, refunding its rent to whoever paid for it
+pub fn close_empty_vault<'info>(
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    rent_payer: &AccountInfo<'info>,
+    game_state: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    vault.reload()?;
+
+    if vault.amount > 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: rent_payer.clone(),
+        authority: game_state.clone(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        cpi_accounts,
+        signer,
+    ))
+}
+
+// Mints a single position token for each side of a game to its player and fixes the supply
+// Each position is a (mint, player's position token account) pair
+pub fn mint_position_tokens<'info>(
+    positions: [(AccountInfo<'info>, AccountInfo<'info>); 2],
+    game_state: &AccountInfo<'info>,
+    position_token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    for (mint, position_account) in positions {
+        let cpi_accounts = MintTo {
+            mint: mint.clone(),
+            to: position_account,
+            authority: game_state.clone(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(position_token_program.clone(), cpi_accounts, signer);
+
+        token_interface::mint_to(cpi_ctx, 1)?;
+
+        let cpi_accounts = SetAuthority {
+            current_authority: game_state.clone(),
+            account_or_mint: mint,
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(position_token_program.clone(), cpi_accounts, signer);
+
+        token_interface::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+    }
+
+    Ok(())
+}
+
+// Burns a settled position token, signed by the game as the mint's permanent delegate so the
+// holder doesn't need to sign, then closes the emptied mint to whoever paid its rent
+pub fn burn_position_token<'info>(
+    mint: &AccountInfo<'info>,
+    position_account: &AccountInfo<'info>,
+    game_state: &AccountInfo<'info>,
+    rent_payer: &AccountInfo<'info>,
+    position_token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint: mint.clone(),
+        from: position_account.clone(),
+        authority: game_state.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(position_token_program.clone(), cpi_accounts, signer);
+
+    token_interface::burn(cpi_ctx, 1)?;
+
+    close_token_account(mint, rent_payer, game_state, position_token_program, signer)
+}
+
+// Closes an empty token account or mint, signed by its close authority
+pub fn close_token_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: account.clone(),
+        destination: destination.clone(),
+        authority: authority.clone(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        cpi_accounts,
+        signer,
+    ))
 }
@@ -1,7 +1,13 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
-use crate::{common::CustomError, state::GameState};
+use crate::{
+    common::CustomError,
+    state::{Config, GameState, PlayerBalance, PlayerProfile, SharedEscrow},
+};
 
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
@@ -9,22 +15,60 @@ pub struct CancelGame<'info> {
     #[account(mut)]
     pub initiator: Signer<'info>,
 
+    /// Pays for the initiator's token account and profile if they don't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Temporary account SOL payouts are unwrapped through, created and closed here
+    #[account(
+        mut,
+        seeds = [b"sol_payout", payer.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
     #[account(
         mut,
-        constraint = initiator_token_account.owner == initiator.key() @ CustomError::InvalidTokenAccount,
-        constraint = initiator_token_account.mint == usdc_mint.key() @ CustomError::InvalidTokenMint,
+        address = game_state.rent_payer @ CustomError::IncorrectRentPayer,
     )]
-    pub initiator_token_account: Account<'info, TokenAccount>,
+    pub rent_payer: SystemAccount<'info>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    #[account(
+        constraint = stake_mint.key() == game_state.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = initiator,
+        associated_token::token_program = token_program,
+    )]
+    pub initiator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The initiator's internal balance, refunded instead for games funded from it
     #[account(
         mut,
-        constraint = vault.mint == usdc_mint.key() @ CustomError::InvalidTokenMint,
-        seeds = [b"game_vault", initiator.key().as_ref(), &game_id.to_le_bytes()],
-        bump
+        seeds = [b"player_balance", initiator.key().as_ref(), stake_mint.key().as_ref()],
+        bump = initiator_balance.bump
+    )]
+    pub initiator_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    #[account(
+        mut,
+        constraint = vault.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+        address = game_state.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint's shared escrow, for games holding their stakes in its vault
+    #[account(
+        mut,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump = shared_escrow.bump
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
 
     #[account(
         mut,
@@ -36,6 +80,22 @@ pub struct CancelGame<'info> {
     )]
     pub game_state: Account<'info, GameState>,
 
-    pub token_program: Program<'info, Token>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", initiator.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub initiator_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Config, Tournament};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CancelTournament<'info> {
+    /// The admin, or anyone once registration closed with too few players
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+}
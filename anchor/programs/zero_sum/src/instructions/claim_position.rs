@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{common::CustomError, state::GameState};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, initiator: Pubkey)]
+pub struct ClaimPosition<'info> {
+    pub claimant: Signer<'info>,
+
+    #[account(
+        constraint = claimant_position_account.owner == claimant.key() @ CustomError::InvalidTokenAccount,
+        constraint = claimant_position_account.amount == 1 @ CustomError::InvalidPositionToken,
+    )]
+    pub claimant_position_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = position_mint.key() == claimant_position_account.mint @ CustomError::InvalidPositionToken,
+    )]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    common::CustomError,
+    state::{Config, PlayerProfile, Referral},
+};
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// Pays for the referrer's token account if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Temporary account SOL payouts are unwrapped through, created and closed here
+    #[account(
+        mut,
+        seeds = [b"sol_payout", payer.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = referrer,
+        associated_token::token_program = token_program,
+    )]
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = referral.referrer == referrer.key() @ CustomError::InvalidReferrer,
+        seeds = [b"referral", player_profile.player.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    /// Profile of the referred player, holding the rewards earned for the stake mint
+    #[account(
+        mut,
+        seeds = [b"player_profile", player_profile.player.as_ref(), stake_mint.key().as_ref()],
+        bump = player_profile.bump
+    )]
+    pub player_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub referral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{common::CustomError, state::Tournament};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct ClaimTournamentPrize<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Pays for the player's token account if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Temporary account SOL payouts are unwrapped through, created and closed here
+    #[account(
+        mut,
+        seeds = [b"sol_payout", payer.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == tournament.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_program,
+    )]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        address = tournament.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub tournament_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    common::CustomError,
+    state::{Config, PriceSources},
+};
+
+#[derive(Accounts)]
+pub struct ClosePriceSources<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ CustomError::NotAdmin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Only used as the key of the market the sources price
+    pub chainlink_feed: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"price_sources", chainlink_feed.key().as_ref()],
+        bump = price_sources.bump
+    )]
+    pub price_sources: Box<Account<'info, PriceSources>>,
+}
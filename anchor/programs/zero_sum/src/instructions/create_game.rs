@@ -1,48 +1,109 @@
 use anchor_lang::{prelude::*, Discriminator};
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{common::CustomError, state::GameState};
+use crate::{
+    common::CustomError,
+    state::{AllowedMint, GameState, PlayerBalance, SessionKey, SharedEscrow},
+};
 
 #[derive(Accounts)]
 #[instruction(game_id: u64)]
 pub struct CreateGame<'info> {
+    /// The initiator's wallet, or a session key authorized by the initiator
+    pub authority: Signer<'info>,
+
+    /// Pays the game's and its vault's rent, refunded to it when the game closes
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(mut)]
-    pub initiator: Signer<'info>,
+    pub initiator: SystemAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"session", initiator.key().as_ref(), authority.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    /// Funds the stake, unless it is funded from the initiator's internal balance
     #[account(
         mut,
         constraint = initiator_token_account.owner == initiator.key() @ CustomError::InvalidTokenAccount,
-        constraint = initiator_token_account.mint == usdc_mint.key() @ CustomError::InvalidTokenMint,
+        constraint = initiator_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub initiator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The initiator's internal balance, to fund the stake from instead
+    #[account(
+        mut,
+        seeds = [b"player_balance", initiator.key().as_ref(), stake_mint.key().as_ref()],
+        bump = initiator_balance.bump
+    )]
+    pub initiator_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    /// The mint's balance vault, holding the stake instead when it is funded from the
+    /// initiator's balance
+    #[account(
+        mut,
+        seeds = [b"balance_vault", stake_mint.key().as_ref()],
+        bump
     )]
-    pub initiator_token_account: Account<'info, TokenAccount>,
+    pub balance_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    #[account(
+        constraint = allowed_mint.enabled @ CustomError::InvalidTokenMint,
+        seeds = [b"allowed_mint", stake_mint.key().as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
 
     #[account(
         init,
-        payer = initiator,
-        token::mint = usdc_mint,
+        payer = payer,
+        token::mint = stake_mint,
         token::authority = game_state,
+        token::token_program = token_program,
         seeds = [b"game_vault", initiator.key().as_ref(), &game_id.to_le_bytes()],
         bump
     )]
-    pub vault: Account<'info, TokenAccount>,
+    /// The game's own vault. Leave empty to hold the stake in the shared escrow vault or
+    /// the balance vault.
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint's shared escrow, for holding the stake in its vault instead
+    #[account(
+        mut,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump = shared_escrow.bump
+    )]
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
+
+    #[account(mut)]
+    pub shared_escrow_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
-        payer = initiator,
+        payer = payer,
         space = GameState::DISCRIMINATOR.len() + GameState::INIT_SPACE,
         seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
         bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub game_state: Box<Account<'info, GameState>>,
 
     /// CHECK: We're reading data from this specified chainlink feed
     pub chainlink_feed: AccountInfo<'info>,
 
     /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
     pub chainlink_program: AccountInfo<'info>,
 
+    /// CHECK: The market's price sources if configured, copied into the game
+    #[account(seeds = [b"price_sources", chainlink_feed.key().as_ref()], bump)]
+    pub price_sources: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,56 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    common::CustomError,
+    state::{AllowedMint, Config, Season},
+};
+
+#[derive(Accounts)]
+#[instruction(season_id: u64)]
+pub struct CreateSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ CustomError::NotAdmin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"allowed_mint", stake_mint.key().as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    /// The mint's current season, required if it has one
+    pub current_season: Option<Box<Account<'info, Season>>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Season::DISCRIMINATOR.len() + Season::INIT_SPACE,
+        seeds = [b"season", stake_mint.key().as_ref(), &season_id.to_le_bytes()],
+        bump
+    )]
+    pub season: Box<Account<'info, Season>>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = season,
+        token::token_program = token_program,
+        seeds = [b"season_vault", season.key().as_ref()],
+        bump
+    )]
+    pub season_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
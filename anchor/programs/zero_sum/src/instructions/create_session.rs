@@ -0,0 +1,36 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{common::CustomError, state::SessionKey};
+
+#[derive(Accounts)]
+#[instruction(session_signer: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pays the session's rent, refunded to it when the session is revoked
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ CustomError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = SessionKey::DISCRIMINATOR.len() + SessionKey::INIT_SPACE,
+        seeds = [b"session", owner.key().as_ref(), session_signer.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
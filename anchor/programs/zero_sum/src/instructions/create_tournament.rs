@@ -0,0 +1,46 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    common::CustomError,
+    state::{Config, Tournament},
+};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ CustomError::NotAdmin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Tournament::DISCRIMINATOR.len() + Tournament::INIT_SPACE,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = tournament,
+        token::token_program = token_program,
+        seeds = [b"tournament_vault", tournament.key().as_ref()],
+        bump
+    )]
+    pub tournament_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    common::CustomError,
+    state::{GameState, Tournament},
+};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64, match_index: u8, game_id: u64)]
+pub struct CreateTournamentGame<'info> {
+    /// The home player of the match, who picks the prediction
+    pub initiator: Signer<'info>,
+
+    /// Pays the match game's and its vault's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        constraint = stake_mint.key() == tournament.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = stake_mint,
+        token::authority = game_state,
+        token::token_program = token_program,
+        seeds = [b"game_vault", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = GameState::DISCRIMINATOR.len() + GameState::INIT_SPACE,
+        seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: We're reading data from this specified chainlink feed
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
+    pub chainlink_program: AccountInfo<'info>,
+
+    /// CHECK: Copied into the match game if the market has price sources configured
+    #[account(seeds = [b"price_sources", chainlink_feed.key().as_ref()], bump)]
+    pub price_sources: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{common::CustomError, state::PlayerBalance};
+
+#[derive(Accounts)]
+pub struct DepositBalance<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pays the owner's balance account rent if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ CustomError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerBalance::DISCRIMINATOR.len() + PlayerBalance::INIT_SPACE,
+        seeds = [b"player_balance", owner.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub owner_balance: Box<Account<'info, PlayerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"balance_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub balance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{common::CustomError, state::HousePool};
+
+#[derive(Accounts)]
+pub struct DepositLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// Pays for the provider's share account if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == provider.key() @ CustomError::InvalidTokenAccount,
+        constraint = provider_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = share_mint,
+        associated_token::authority = provider,
+        associated_token::token_program = token_program,
+    )]
+    pub provider_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"house_pool", stake_mint.key().as_ref()],
+        bump = house_pool.bump
+    )]
+    pub house_pool: Box<Account<'info, HousePool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = stake_mint,
+        associated_token::authority = house_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub house_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = house_pool.share_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
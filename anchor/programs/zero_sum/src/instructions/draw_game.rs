@@ -1,42 +1,252 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
-use crate::{common::CustomError, state::GameState};
+use crate::{
+    common::CustomError,
+    state::{
+        Config, GameState, HousePool, PlayerBalance, PlayerProfile, Season, SessionKey,
+        SharedEscrow,
+    },
+};
 
 #[derive(Accounts)]
 #[instruction(game_id: u64, initiator: Pubkey)]
 pub struct DrawGame<'info> {
+    /// The player's wallet, or a session key authorized by the player. Anyone can settle
+    /// a game for the house pool.
+    pub authority: Signer<'info>,
+
+    /// Pays for any token accounts and profiles the settlement needs that don't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Temporary account SOL payouts are unwrapped through, created and closed here
+    #[account(
+        mut,
+        seeds = [b"sol_payout", payer.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.rent_payer @ CustomError::IncorrectRentPayer,
+    )]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// Paid for the joining side's accounts, refunded as the position mints are closed
+    #[account(
+        mut,
+        constraint = Some(join_rent_payer.key()) == game_state.join_rent_payer @ CustomError::IncorrectRentPayer,
+    )]
+    pub join_rent_payer: SystemAccount<'info>,
+
+    /// CHECK: The wallet of either side's current holder, or the house pool
     #[account(mut)]
-    pub player: Signer<'info>,
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"session", player.key().as_ref(), authority.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
 
     #[account(
         mut,
         seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
         bump = game_state.bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub game_state: Box<Account<'info, GameState>>,
+
+    #[account(
+        mut,
+        address = game_state.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The mint's shared escrow, for games holding their stakes in its vault
+    #[account(
+        mut,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump = shared_escrow.bump
+    )]
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
+
+    /// The mint's house pool, required when it is a player in the game
+    #[account(
+        mut,
+        seeds = [b"house_pool", stake_mint.key().as_ref()],
+        bump = house_pool.bump
+    )]
+    pub house_pool: Option<Box<Account<'info, HousePool>>>,
+
+    #[account(
+        constraint = stake_mint.key() == game_state.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// CHECK: Current holder of the initiator's side
     #[account(
         mut,
-        seeds = [b"game_vault", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        address = game_state.initiator_holder @ CustomError::NotAuthorized,
+    )]
+    pub initiator_holder: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = initiator_holder,
+        associated_token::token_program = token_program,
+    )]
+    pub initiator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The internal balance of the initiator's holder, paid instead for games funded from
+    /// balances
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerBalance::DISCRIMINATOR.len() + PlayerBalance::INIT_SPACE,
+        seeds = [b"player_balance", initiator_holder.key().as_ref(), stake_mint.key().as_ref()],
         bump
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub initiator_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    #[account(
+        mut,
+        constraint = Some(initiator_position_mint.key()) == game_state.initiator_position_mint @ CustomError::InvalidPositionToken,
+    )]
+    pub initiator_position_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// Token account holding the initiator's position token, whoever owns it, burned with it
+    /// if passed
     #[account(
         mut,
-        constraint = initiator_token_account.owner == game_state.initiator && initiator_token_account.mint == usdc_mint.key() @ CustomError::InvalidTokenAccount
+        constraint = initiator_position_account.mint == initiator_position_mint.key() @ CustomError::InvalidPositionToken,
+        constraint = initiator_position_account.amount == 1 @ CustomError::InvalidPositionToken,
     )]
-    pub initiator_token_account: Account<'info, TokenAccount>,
+    pub initiator_position_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
+    /// CHECK: Current holder of the challenger's side
     #[account(
         mut,
-        constraint = game_state.challenger.is_some() && challenger_token_account.owner == game_state.challenger.unwrap() && challenger_token_account.mint == usdc_mint.key() @ CustomError::InvalidTokenAccount
+        constraint = Some(challenger_holder.key()) == game_state.challenger_holder @ CustomError::NotAuthorized,
     )]
-    pub challenger_token_account: Account<'info, TokenAccount>,
+    pub challenger_holder: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = challenger_holder,
+        associated_token::token_program = token_program,
+    )]
+    pub challenger_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The internal balance of the challenger's holder, paid instead for games funded from
+    /// balances
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerBalance::DISCRIMINATOR.len() + PlayerBalance::INIT_SPACE,
+        seeds = [b"player_balance", challenger_holder.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub challenger_balance: Option<Box<Account<'info, PlayerBalance>>>,
+
+    #[account(
+        mut,
+        constraint = Some(challenger_position_mint.key()) == game_state.challenger_position_mint @ CustomError::InvalidPositionToken,
+    )]
+    pub challenger_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token account holding the challenger's position token, whoever owns it, burned with it
+    /// if passed
+    #[account(
+        mut,
+        constraint = challenger_position_account.mint == challenger_position_mint.key() @ CustomError::InvalidPositionToken,
+        constraint = challenger_position_account.amount == 1 @ CustomError::InvalidPositionToken,
+    )]
+    pub challenger_position_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", game_state.initiator.as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub initiator_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", game_state.challenger.unwrap_or_default().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Only used as the owner of the fee token account
+    #[account(address = config.treasury @ CustomError::InvalidTreasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The stake mint's allowed mint entry, read for its current season if it exists
+    #[account(seeds = [b"allowed_mint", stake_mint.key().as_ref()], bump)]
+    pub allowed_mint: UncheckedAccount<'info>,
+
+    /// The stake mint's current season, required whenever it has one
+    #[account(mut)]
+    pub season: Option<Box<Account<'info, Season>>>,
+
+    #[account(mut)]
+    pub season_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: The initiator's referral. Their referrer is credited whenever it exists.
+    #[account(seeds = [b"referral", game_state.initiator.as_ref()], bump)]
+    pub initiator_referral: UncheckedAccount<'info>,
+
+    /// CHECK: The challenger's referral. Their referrer is credited whenever it exists.
+    #[account(seeds = [b"referral", game_state.challenger.unwrap_or_default().as_ref()], bump)]
+    pub challenger_referral: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub referral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: We're reading data from this specified chainlink feed. Required for games
+    /// that split the pot by the price at timeout.
+    #[account(address = game_state.price_feed @ CustomError::InvalidPriceFeed)]
+    pub chainlink_feed: Option<UncheckedAccount<'info>>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
+    pub chainlink_program: Option<UncheckedAccount<'info>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
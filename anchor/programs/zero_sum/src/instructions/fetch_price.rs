@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::common::CustomError;
+
 #[derive(Accounts)]
 pub struct FetchPrice<'info> {
     /// CHECK: We're reading data from this specified chainlink feed
     pub chainlink_feed: AccountInfo<'info>,
 
     /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
     pub chainlink_program: AccountInfo<'info>,
 }
@@ -0,0 +1,143 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    common::CustomError,
+    state::{GameState, HousePool, PlayerProfile, SharedEscrow},
+};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, initiator: Pubkey)]
+pub struct HouseJoinGame<'info> {
+    /// Pays for the position mints and accounts, e.g. the keeper cranking the join
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = initiator @ CustomError::IncorrectInitiator)]
+    pub initiator_wallet: SystemAccount<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == game_state.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"house_pool", stake_mint.key().as_ref()],
+        bump = house_pool.bump
+    )]
+    pub house_pool: Box<Account<'info, HousePool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = stake_mint,
+        associated_token::authority = house_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub house_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vault.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+        address = game_state.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint's shared escrow, for games holding their stakes in its vault
+    #[account(
+        mut,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump = shared_escrow.bump
+    )]
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
+
+    #[account(
+        mut,
+        constraint = game_state.is_creator(initiator) @ CustomError::IncorrectInitiator,
+        constraint = game_state.is_correct_game_id(game_id) @ CustomError::IncorrectGameId,
+        seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump = game_state.bump
+    )]
+    pub game_state: Box<Account<'info, GameState>>,
+
+    /// Position token for the initiator's side, with the game as permanent delegate so
+    /// settlement can burn it
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::token_program = position_token_program,
+        extensions::permanent_delegate::delegate = game_state,
+        extensions::close_authority::authority = game_state,
+        seeds = [b"initiator_position", game_state.key().as_ref()],
+        bump
+    )]
+    pub initiator_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Position token for the challenger's side, burnable by the game in the same way
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::token_program = position_token_program,
+        extensions::permanent_delegate::delegate = game_state,
+        extensions::close_authority::authority = game_state,
+        seeds = [b"challenger_position", game_state.key().as_ref()],
+        bump
+    )]
+    pub challenger_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = initiator_position_mint,
+        associated_token::authority = initiator_wallet,
+        associated_token::token_program = position_token_program,
+    )]
+    pub initiator_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = challenger_position_mint,
+        associated_token::authority = house_pool,
+        associated_token::token_program = position_token_program,
+    )]
+    pub challenger_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", initiator_wallet.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub initiator_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", house_pool.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub house_pool_profile: Box<Account<'info, PlayerProfile>>,
+
+    /// CHECK: We're reading data from this specified chainlink feed
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
+    pub chainlink_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
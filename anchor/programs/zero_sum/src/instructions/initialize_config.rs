@@ -0,0 +1,30 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{common::CustomError, program::ZeroSum, state::Config};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::DISCRIMINATOR.len() + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ CustomError::NotAdmin,
+    )]
+    pub program: Program<'info, ZeroSum>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::NotAdmin,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,47 +1,161 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
-use crate::{common::CustomError, state::GameState};
+use crate::{
+    common::CustomError,
+    state::{GameState, PlayerBalance, PlayerProfile, SessionKey, SharedEscrow},
+};
 
 #[derive(Accounts)]
 #[instruction(game_id: u64, initiator: Pubkey)]
 pub struct JoinGame<'info> {
+    /// The challenger's wallet, or a session key authorized by the challenger
+    pub authority: Signer<'info>,
+
+    /// Pays for the position mints and accounts, e.g. a sponsoring relayer or the challenger
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(mut)]
-    pub challenger: Signer<'info>,
+    pub challenger: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", challenger.key().as_ref(), authority.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionKey>>,
+
+    #[account(address = initiator @ CustomError::IncorrectInitiator)]
+    pub initiator_wallet: SystemAccount<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == game_state.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
 
+    /// Funds the stake, unless it is funded from the challenger's internal balance
     #[account(
         mut,
         constraint = challenger_token_account.owner == challenger.key() @ CustomError::InvalidTokenAccount,
-        constraint = challenger_token_account.mint == usdc_mint.key() @ CustomError::InvalidTokenMint,
+        constraint = challenger_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
     )]
-    pub challenger_token_account: Account<'info, TokenAccount>,
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    pub usdc_mint: Account<'info, Mint>,
+    /// The challenger's internal balance, to fund the stake from instead. Only games held in
+    /// the balance vault can be joined this way.
+    #[account(
+        mut,
+        seeds = [b"player_balance", challenger.key().as_ref(), stake_mint.key().as_ref()],
+        bump = challenger_balance.bump
+    )]
+    pub challenger_balance: Option<Box<Account<'info, PlayerBalance>>>,
 
     #[account(
         mut,
-        constraint = vault.mint == usdc_mint.key() @ CustomError::InvalidTokenMint,
-        seeds = [b"game_vault", initiator.key().as_ref(), &game_id.to_le_bytes()],
-        bump
+        constraint = vault.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+        address = game_state.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint's shared escrow, for games holding their stakes in its vault
+    #[account(
+        mut,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump = shared_escrow.bump
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
 
     #[account(
         mut,
         constraint = !game_state.is_initiator(challenger.key()) @ CustomError::CannotJoinOwnGame,
-        constraint = game_state.is_initiator(initiator) @ CustomError::IncorrectInitiator,
+        constraint = game_state.is_creator(initiator) @ CustomError::IncorrectInitiator,
         constraint = game_state.is_correct_game_id(game_id) @ CustomError::IncorrectGameId,
         seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
         bump = game_state.bump
     )]
-    pub game_state: Account<'info, GameState>,
+    pub game_state: Box<Account<'info, GameState>>,
+
+    /// Position token for the initiator's side. The game is its permanent delegate so
+    /// settlement can burn both sides' tokens in one call, without the other side's holder
+    /// signing, wherever the tokens have been traded to.
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::token_program = position_token_program,
+        extensions::permanent_delegate::delegate = game_state,
+        extensions::close_authority::authority = game_state,
+        seeds = [b"initiator_position", game_state.key().as_ref()],
+        bump
+    )]
+    pub initiator_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Position token for the challenger's side, burnable by the game in the same way
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::token_program = position_token_program,
+        extensions::permanent_delegate::delegate = game_state,
+        extensions::close_authority::authority = game_state,
+        seeds = [b"challenger_position", game_state.key().as_ref()],
+        bump
+    )]
+    pub challenger_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = initiator_position_mint,
+        associated_token::authority = initiator_wallet,
+        associated_token::token_program = position_token_program,
+    )]
+    pub initiator_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = challenger_position_mint,
+        associated_token::authority = challenger,
+        associated_token::token_program = position_token_program,
+    )]
+    pub challenger_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", initiator_wallet.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub initiator_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", challenger.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Box<Account<'info, PlayerProfile>>,
 
     /// CHECK: We're reading data from this specified chainlink feed
+    #[account(address = game_state.price_feed @ CustomError::InvalidPriceFeed)]
     pub chainlink_feed: AccountInfo<'info>,
 
     /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
     pub chainlink_program: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
 }
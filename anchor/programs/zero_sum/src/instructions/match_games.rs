@@ -0,0 +1,158 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    common::CustomError,
+    state::{Config, GameState, PlayerProfile, SharedEscrow},
+};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, initiator: Pubkey, matched_game_id: u64, matched_initiator: Pubkey)]
+pub struct MatchGames<'info> {
+    /// Pays for the position mints and accounts, e.g. the keeper matching the games
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(address = initiator @ CustomError::IncorrectInitiator)]
+    pub initiator_wallet: SystemAccount<'info>,
+
+    #[account(address = matched_initiator @ CustomError::IncorrectInitiator)]
+    pub matched_initiator_wallet: SystemAccount<'info>,
+
+    /// Paid the matched game's rent; refunded when its accounts close
+    #[account(
+        mut,
+        address = matched_game_state.rent_payer @ CustomError::IncorrectRentPayer,
+    )]
+    pub matched_rent_payer: SystemAccount<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == game_state.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = vault.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+        address = game_state.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = matched_vault.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+        address = matched_game_state.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub matched_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The mint's shared escrow, for games holding their stakes in its vault
+    #[account(
+        mut,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump = shared_escrow.bump
+    )]
+    pub shared_escrow: Option<Account<'info, SharedEscrow>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump = game_state.bump
+    )]
+    pub game_state: Box<Account<'info, GameState>>,
+
+    /// Merged into `game_state` and closed
+    #[account(
+        mut,
+        close = matched_rent_payer,
+        seeds = [b"game_state", matched_initiator.key().as_ref(), &matched_game_id.to_le_bytes()],
+        bump = matched_game_state.bump
+    )]
+    pub matched_game_state: Box<Account<'info, GameState>>,
+
+    /// Position token for the initiator's side, with the game as permanent delegate so
+    /// settlement can burn it
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::token_program = position_token_program,
+        extensions::permanent_delegate::delegate = game_state,
+        extensions::close_authority::authority = game_state,
+        seeds = [b"initiator_position", game_state.key().as_ref()],
+        bump
+    )]
+    pub initiator_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Position token for the challenger's side, burnable by the game in the same way
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = game_state,
+        mint::token_program = position_token_program,
+        extensions::permanent_delegate::delegate = game_state,
+        extensions::close_authority::authority = game_state,
+        seeds = [b"challenger_position", game_state.key().as_ref()],
+        bump
+    )]
+    pub challenger_position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = initiator_position_mint,
+        associated_token::authority = initiator_wallet,
+        associated_token::token_program = position_token_program,
+    )]
+    pub initiator_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = challenger_position_mint,
+        associated_token::authority = matched_initiator_wallet,
+        associated_token::token_program = position_token_program,
+    )]
+    pub challenger_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", initiator_wallet.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub initiator_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PlayerProfile::DISCRIMINATOR.len() + PlayerProfile::INIT_SPACE,
+        seeds = [b"player_profile", matched_initiator_wallet.key().as_ref(), stake_mint.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Box<Account<'info, PlayerProfile>>,
+
+    /// CHECK: We're reading data from this specified chainlink feed
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
+    pub chainlink_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
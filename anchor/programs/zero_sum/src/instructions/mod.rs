@@ -1,13 +1,75 @@
 mod cancel_game;
+mod cancel_tournament;
+mod claim_position;
+mod claim_referral_rewards;
+mod claim_season_prize;
+mod claim_tournament_prize;
+mod claim_tournament_refund;
 mod close_game;
+mod close_price_sources;
 mod create_game;
+mod create_season;
+mod create_session;
+mod create_tournament;
+mod create_tournament_game;
+mod declare_oracle_outage;
+mod deposit_balance;
+mod deposit_liquidity;
 mod draw_game;
 mod fetch_price;
+mod house_join_game;
+mod initialize_config;
 mod join_game;
+mod match_games;
+mod refresh_game;
+mod register_for_tournament;
+mod register_referrer;
+mod report_tournament_match;
+mod revoke_session;
+mod set_allowed_mint;
+mod set_house_pool;
+mod set_price_sources;
+mod start_game;
+mod start_tournament;
+mod transfer_position;
+mod update_config;
+mod withdraw_balance;
+mod withdraw_liquidity;
 
 pub use cancel_game::*;
+pub use cancel_tournament::*;
+pub use claim_position::*;
+pub use claim_referral_rewards::*;
+pub use claim_season_prize::*;
+pub use claim_tournament_prize::*;
+pub use claim_tournament_refund::*;
 pub use close_game::*;
+pub use close_price_sources::*;
 pub use create_game::*;
+pub use create_season::*;
+pub use create_session::*;
+pub use create_tournament::*;
+pub use create_tournament_game::*;
+pub use declare_oracle_outage::*;
+pub use deposit_balance::*;
+pub use deposit_liquidity::*;
 pub use draw_game::*;
 pub use fetch_price::*;
+pub use house_join_game::*;
+pub use initialize_config::*;
 pub use join_game::*;
+pub use match_games::*;
+pub use refresh_game::*;
+pub use register_for_tournament::*;
+pub use register_referrer::*;
+pub use report_tournament_match::*;
+pub use revoke_session::*;
+pub use set_allowed_mint::*;
+pub use set_house_pool::*;
+pub use set_price_sources::*;
+pub use start_game::*;
+pub use start_tournament::*;
+pub use transfer_position::*;
+pub use update_config::*;
+pub use withdraw_balance::*;
+pub use withdraw_liquidity::*;
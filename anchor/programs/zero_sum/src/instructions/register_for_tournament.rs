@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{common::CustomError, state::Tournament};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct RegisterForTournament<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == tournament.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key() @ CustomError::InvalidTokenAccount,
+        constraint = player_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub player_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    #[account(
+        mut,
+        address = tournament.vault @ CustomError::InvalidTokenAccount,
+    )]
+    pub tournament_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,23 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::state::Referral;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    pub player: Signer<'info>,
+
+    /// Pays the referral account's rent, e.g. a relayer onboarding the player
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Referral::DISCRIMINATOR.len() + Referral::INIT_SPACE,
+        seeds = [b"referral", player.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>,
+}
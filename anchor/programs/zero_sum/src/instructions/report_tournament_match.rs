@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::state::{GameState, Tournament};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct ReportTournamentMatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+
+    /// The match's game, omitted if the home player never created one. It is closed once
+    /// the match is decided.
+    #[account(mut)]
+    pub game_state: Option<Account<'info, GameState>>,
+
+    /// Receives the game's rent, required with the game
+    #[account(mut)]
+    pub rent_payer: Option<SystemAccount<'info>>,
+
+    /// The game's empty vault, required to cancel a game the away player never joined
+    #[account(mut)]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{common::CustomError, state::SessionKey};
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Temporary token account the unused allowance is unwrapped through. It is
+    /// created and closed within the instruction.
+    #[account(
+        mut,
+        seeds = [b"sol_payout", owner.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = session.rent_payer @ CustomError::IncorrectRentPayer,
+    )]
+    pub rent_payer: SystemAccount<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == session.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ CustomError::InvalidTokenAccount,
+        constraint = owner_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"session", owner.key().as_ref(), session.session_signer.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
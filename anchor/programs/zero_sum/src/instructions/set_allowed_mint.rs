@@ -0,0 +1,79 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    common::CustomError,
+    state::{AllowedMint, Config, SharedEscrow},
+};
+
+#[derive(Accounts)]
+pub struct SetAllowedMint<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ CustomError::NotAdmin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = AllowedMint::DISCRIMINATOR.len() + AllowedMint::INIT_SPACE,
+        seeds = [b"allowed_mint", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+
+    /// Holds referral rewards in the mint until referrers withdraw them
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = config,
+        token::token_program = token_program,
+        seeds = [b"referral_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub referral_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Holds players' internal balances in the mint
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = config,
+        token::token_program = token_program,
+        seeds = [b"balance_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub balance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = SharedEscrow::DISCRIMINATOR.len() + SharedEscrow::INIT_SPACE,
+        seeds = [b"shared_escrow", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub shared_escrow: Box<Account<'info, SharedEscrow>>,
+
+    /// Holds the stakes of games escrowed in the mint's shared vault
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = shared_escrow,
+        token::token_program = token_program,
+        seeds = [b"shared_escrow_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub shared_escrow_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,59 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    common::CustomError,
+    state::{Config, HousePool},
+};
+
+#[derive(Accounts)]
+pub struct SetHousePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ CustomError::NotAdmin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = HousePool::DISCRIMINATOR.len() + HousePool::INIT_SPACE,
+        seeds = [b"house_pool", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub house_pool: Box<Account<'info, HousePool>>,
+
+    /// Holds the pool's free liquidity and receives its payouts from settled games
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = stake_mint,
+        associated_token::authority = house_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub house_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        mint::decimals = stake_mint.decimals,
+        mint::authority = house_pool,
+        mint::token_program = token_program,
+        seeds = [b"house_pool_shares", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
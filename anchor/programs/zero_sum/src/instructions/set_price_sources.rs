@@ -0,0 +1,33 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    common::CustomError,
+    state::{Config, PriceSources},
+};
+
+#[derive(Accounts)]
+pub struct SetPriceSources<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ CustomError::NotAdmin,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Only used as the key of the market the sources price
+    pub chainlink_feed: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PriceSources::DISCRIMINATOR.len() + PriceSources::INIT_SPACE,
+        seeds = [b"price_sources", chainlink_feed.key().as_ref()],
+        bump
+    )]
+    pub price_sources: Box<Account<'info, PriceSources>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{common::CustomError, state::GameState};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, initiator: Pubkey)]
+pub struct StartGame<'info> {
+    #[account(
+        mut,
+        constraint = game_state.is_creator(initiator) @ CustomError::IncorrectInitiator,
+        constraint = game_state.is_correct_game_id(game_id) @ CustomError::IncorrectGameId,
+        seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: We're reading data from this specified chainlink feed
+    #[account(address = game_state.price_feed @ CustomError::InvalidPriceFeed)]
+    pub chainlink_feed: AccountInfo<'info>,
+
+    /// CHECK: This is the Chainlink program library on Devnet
+    #[account(address = chainlink_solana::ID @ CustomError::InvalidPriceFeed)]
+    pub chainlink_program: AccountInfo<'info>,
+}
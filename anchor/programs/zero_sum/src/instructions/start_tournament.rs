@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Tournament;
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct StartTournament<'info> {
+    #[account(
+        mut,
+        seeds = [b"tournament".as_ref(), &tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Box<Account<'info, Tournament>>,
+}
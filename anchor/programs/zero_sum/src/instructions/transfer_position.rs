@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{common::CustomError, state::GameState};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64, initiator: Pubkey)]
+pub struct TransferPosition<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(mut)]
+    pub new_holder: Signer<'info>,
+
+    /// Pays for the token accounts of either holder that don't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = stake_mint.key() == game_state.stake_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_program,
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = new_holder_token_account.owner == new_holder.key() @ CustomError::InvalidTokenAccount,
+        constraint = new_holder_token_account.mint == stake_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub new_holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = holder_position_account.owner == holder.key() @ CustomError::InvalidTokenAccount,
+        constraint = holder_position_account.amount == 1 @ CustomError::InvalidPositionToken,
+    )]
+    pub holder_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = position_mint,
+        associated_token::authority = new_holder,
+        associated_token::token_program = position_token_program,
+    )]
+    pub new_holder_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = position_mint.key() == holder_position_account.mint @ CustomError::InvalidPositionToken,
+    )]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = game_state.is_active() @ CustomError::GameNotActive,
+        constraint = game_state.is_creator(initiator) @ CustomError::IncorrectInitiator,
+        constraint = game_state.is_correct_game_id(game_id) @ CustomError::IncorrectGameId,
+        seeds = [b"game_state", initiator.key().as_ref(), &game_id.to_le_bytes()],
+        bump = game_state.bump
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub position_token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
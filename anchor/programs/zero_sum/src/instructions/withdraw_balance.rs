@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::state::{Config, PlayerBalance};
+
+#[derive(Accounts)]
+pub struct WithdrawBalance<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pays for the owner's token account if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Temporary account SOL payouts are unwrapped through, created and closed here
+    #[account(
+        mut,
+        seeds = [b"sol_payout", payer.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"player_balance", owner.key().as_ref(), stake_mint.key().as_ref()],
+        bump = owner_balance.bump
+    )]
+    pub owner_balance: Box<Account<'info, PlayerBalance>>,
+
+    #[account(
+        mut,
+        seeds = [b"balance_vault", stake_mint.key().as_ref()],
+        bump
+    )]
+    pub balance_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{common::CustomError, state::HousePool};
+
+#[derive(Accounts)]
+pub struct WithdrawLiquidity<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// Pays for the provider's token account if it doesn't exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Temporary account SOL payouts are unwrapped through, created and closed here
+    #[account(
+        mut,
+        seeds = [b"sol_payout", payer.key().as_ref()],
+        bump
+    )]
+    pub sol_payout: UncheckedAccount<'info>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = stake_mint,
+        associated_token::authority = provider,
+        associated_token::token_program = token_program,
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = provider_share_account.owner == provider.key() @ CustomError::InvalidTokenAccount,
+        constraint = provider_share_account.mint == share_mint.key() @ CustomError::InvalidTokenMint,
+    )]
+    pub provider_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"house_pool", stake_mint.key().as_ref()],
+        bump = house_pool.bump
+    )]
+    pub house_pool: Box<Account<'info, HousePool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = stake_mint,
+        associated_token::authority = house_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub house_pool_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = house_pool.share_mint @ CustomError::InvalidTokenMint,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -1,7 +1,8 @@
 #![allow(clippy::result_large_err)]
+#![allow(unexpected_cfgs)]
 
-use anchor_lang::prelude::*;
-use anchor_spl::token::{self, TransferChecked};
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token_interface::{self, Approve, Burn, MintTo, Revoke, TransferChecked};
 
 pub mod common;
 pub mod instructions;
@@ -9,6 +10,7 @@ pub mod state;
 
 use crate::common::*;
 use crate::instructions::*;
+use crate::state::PriceSource;
 
 declare_id!("4vFwvetVyScrRU5sYKrK2bGJxEpqQY8aaaGik2pCeRuG");
 
@@ -32,10 +34,134 @@ pub mod zero_sum {
         Ok(())
     }
 
+    /// Initializes the protocol config.
+    ///
+    /// Only the program's upgrade authority can initialize the config and becomes its admin.
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = ctx.accounts.admin.key();
+        config.treasury = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+
+        Ok(())
+    }
+
+    /// Updates the protocol fee settings.
+    ///
+    /// The fee is taken from the pot of every game settled through `close_game`. Drawn and
+    /// cancelled games are refunded in full.
+    ///
+    /// This function:
+    /// - Ensures the caller is the admin
+    /// - Validates the fee and season share are within bounds
+    /// - Sets the treasury, protocol fee and the season and referral shares
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        treasury: Pubkey,
+        protocol_fee_bps: u16,
+        season_share_bps: u16,
+        referral_share_bps: u16,
+    ) -> Result<()> {
+        // Referrers of both players together receive at most `referral_share_bps` of the fee
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS
+                && season_share_bps as u32 + referral_share_bps as u32 <= BPS_DENOMINATOR as u32,
+            CustomError::InvalidFeeBps
+        );
+
+        let config = &mut ctx.accounts.config;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        config.treasury = treasury;
+        config.protocol_fee_bps = protocol_fee_bps;
+        config.season_share_bps = season_share_bps;
+        config.referral_share_bps = referral_share_bps;
+
+        emit!(ConfigUpdated {
+            treasury,
+            protocol_fee_bps,
+            season_share_bps,
+            referral_share_bps,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Adds or updates a token mint on the stake allowlist.
+    ///
+    /// Both SPL Token and Token-2022 mints are supported. Token-2022 transfer fees are
+    /// covered by the depositing player so each vault holds the full entry amount.
+    /// Allowlisting the wrapped SOL mint enables native SOL games: stakes are wrapped from
+    /// and unwrapped back to players' wallets, with the entry amount set in lamports.
+    ///
+    /// This function:
+    /// - Ensures the caller is the admin
+    /// - Sets the per-player entry amount (in base units) for the mint
+    /// - Enables or disables new games with the mint
+    /// - Creates the mint's referral reward, balance and shared escrow vaults on first use
+    pub fn set_allowed_mint(
+        ctx: Context<SetAllowedMint>,
+        entry_amount: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(entry_amount > 0, CustomError::InvalidEntryAmount);
+
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        allowed_mint.mint = ctx.accounts.stake_mint.key();
+        allowed_mint.entry_amount = entry_amount;
+        allowed_mint.enabled = enabled;
+        allowed_mint.bump = ctx.bumps.allowed_mint;
+
+        let shared_escrow = &mut ctx.accounts.shared_escrow;
+        shared_escrow.stake_mint = allowed_mint.mint;
+        shared_escrow.vault = ctx.accounts.shared_escrow_vault.key();
+        shared_escrow.bump = ctx.bumps.shared_escrow;
+
+        emit!(AllowedMintUpdated {
+            mint: allowed_mint.mint,
+            entry_amount,
+            enabled,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Creates a game, staking the initiator's entry amount on their prediction.
+    ///
+    /// Games normally take their initial price when created. A game with a `start_at`
+    /// in the future can be joined straight away but only captures its initial price once
+    /// `start_game` is called within `START_GAME_WINDOW_SECONDS` of that time; the win
+    /// threshold and timeout are measured from there. A joined game that misses its start
+    /// window can only be refunded through `draw_game`.
+    ///
+    /// With `anchor_price_at_join` the initial price is taken again when a challenger
+    /// joins, so neither side starts with the drift in between in its favour.
+    ///
+    /// `timeout_policy` decides how the pot is split if the game times out before either
+    /// side reaches the win threshold. `increase_threshold_bps` and `decrease_threshold_bps`
+    /// set the rise and fall each side needs to win, defaulting to 5%. Both must exceed the
+    /// 1% the price can move before the game can no longer be joined. `win_condition` can
+    /// require the threshold to hold over several oracle rounds or a time-weighted window.
+    ///
+    /// The stake comes from the initiator's token account, or from their internal balance
+    /// when `initiator_balance` is passed. Stakes from a balance stay in the mint's balance
+    /// vault, and the game settles into its holders' balances rather than their wallets.
+    #[allow(clippy::too_many_arguments)]
     pub fn create_game(
         ctx: Context<CreateGame>,
         game_id: u64,
         prediction: PricePrediction,
+        start_at: Option<i64>,
+        anchor_price_at_join: bool,
+        timeout_policy: TimeoutPolicy,
+        increase_threshold_bps: Option<u16>,
+        decrease_threshold_bps: Option<u16>,
+        win_condition: WinCondition,
     ) -> Result<()> {
         require!(
             prediction == PricePrediction::Increase || prediction == PricePrediction::Decrease,
@@ -45,37 +171,139 @@ pub mod zero_sum {
         let game_state = &mut ctx.accounts.game_state;
         let current_time = Clock::get()?.unix_timestamp;
 
-        // Get price data from Chainlink
-        let initial_price = get_chainlink_price(
+        if let Some(start_at) = start_at {
+            require!(start_at > current_time, CustomError::InvalidStartTime);
+        }
+
+        let increase_threshold_bps = increase_threshold_bps.unwrap_or(WIN_PRICE_THRESHOLD_BPS);
+        let decrease_threshold_bps = decrease_threshold_bps.unwrap_or(WIN_PRICE_THRESHOLD_BPS);
+
+        // Each threshold must be beyond the movement a game can still be joined at, so no
+        // side can be joined once it has already won
+        for threshold_bps in [increase_threshold_bps, decrease_threshold_bps] {
+            require!(
+                threshold_bps as f64 > MAX_JOIN_PRICE_MOVEMENT * BPS_DENOMINATOR as f64
+                    && threshold_bps <= MAX_WIN_PRICE_THRESHOLD_BPS,
+                CustomError::InvalidWinThreshold
+            );
+        }
+
+        match win_condition {
+            WinCondition::Instant => {}
+            WinCondition::ConsecutiveRounds { rounds } => require!(
+                rounds >= 2 && rounds as usize <= MAX_WIN_CONDITION_ROUNDS,
+                CustomError::InvalidWinCondition
+            ),
+            WinCondition::TimeWeighted { window_seconds } => require!(
+                window_seconds > 0 && (window_seconds as i64) < GAME_TIMEOUT_SECONDS,
+                CustomError::InvalidWinCondition
+            ),
+        }
+
+        authorize_player(
+            ctx.accounts.authority.key,
+            ctx.accounts.initiator.key,
+            ctx.accounts.session.as_deref(),
+            SESSION_ACTION_CREATE_GAME,
+            current_time,
+        )?;
+
+        // The game keeps the market's price sources as they are now, so reconfiguring them
+        // only affects new games
+        let price_sources = load_price_sources(&ctx.accounts.price_sources)?;
+
+        // Price the market now. Scheduled games only check the price is available for now.
+        let current_price = get_price(
             &ctx.accounts.chainlink_program,
             &ctx.accounts.chainlink_feed,
+            price_sources.as_ref(),
+            ctx.remaining_accounts,
             current_time,
         )?;
+        let initial_price = if start_at.is_some() {
+            0.0
+        } else {
+            current_price
+        };
 
         game_state.game_id = game_id;
         game_state.initiator = ctx.accounts.initiator.key();
+        game_state.initiator_holder = game_state.initiator;
         game_state.initiator_prediction = prediction;
-        game_state.entry_amount = usdc_to_on_chain_amount(ENTRY_AMOUNT_USDC);
+        game_state.stake_mint = ctx.accounts.stake_mint.key();
+        game_state.entry_amount = ctx.accounts.allowed_mint.entry_amount;
         game_state.initial_price = initial_price;
+        game_state.price_feed = ctx.accounts.chainlink_feed.key();
+        game_state.price_sources = price_sources;
         game_state.created_at = current_time;
+        game_state.start_at = start_at;
+        game_state.anchor_price_at_join = anchor_price_at_join;
+        game_state.timeout_policy = timeout_policy;
+        game_state.increase_threshold_bps = increase_threshold_bps;
+        game_state.decrease_threshold_bps = decrease_threshold_bps;
+        game_state.win_condition = win_condition;
         game_state.status = GameStatus::Pending;
+        game_state.rent_payer = ctx.accounts.payer.key();
         game_state.bump = ctx.bumps.game_state;
 
-        // Transfer entry amount into escrow / vault account
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = TransferChecked {
-            from: ctx.accounts.initiator_token_account.to_account_info(),
-            to: ctx.accounts.vault.to_account_info(),
-            mint: ctx.accounts.usdc_mint.to_account_info(),
-            authority: ctx.accounts.initiator.to_account_info(),
+        // Hold the stake in the game's own vault, the mint's shared escrow vault, or the
+        // mint's balance vault when it is funded from the initiator's internal balance
+        let funded_from_balance = ctx.accounts.initiator_balance.is_some();
+        let vault = match (
+            ctx.accounts.vault.as_mut(),
+            ctx.accounts.shared_escrow.as_deref(),
+            ctx.accounts.shared_escrow_vault.as_mut(),
+            ctx.accounts.balance_vault.as_mut(),
+        ) {
+            (Some(vault), None, None, None) if !funded_from_balance => vault,
+            (None, Some(shared_escrow), Some(shared_escrow_vault), None)
+                if !funded_from_balance && shared_escrow_vault.key() == shared_escrow.vault =>
+            {
+                shared_escrow_vault
+            }
+            (None, None, None, Some(balance_vault)) if funded_from_balance => balance_vault,
+            _ => return err!(CustomError::InvalidEscrow),
         };
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-        token::transfer_checked(
-            cpi_ctx,
-            game_state.entry_amount,
-            ctx.accounts.usdc_mint.decimals,
-        )?;
+        game_state.vault = vault.key();
+        game_state.shared_escrow = ctx.accounts.shared_escrow.is_some();
+        game_state.balance_escrow = funded_from_balance;
+        let entry_amount = game_state.entry_amount;
+        game_state.lock_escrow(entry_amount, ctx.accounts.shared_escrow.as_deref_mut())?;
+
+        // Stake the entry amount from the initiator's internal balance, or transfer it into
+        // escrow / vault account from their token account
+        match ctx.accounts.initiator_balance.as_mut() {
+            Some(initiator_balance) => {
+                stake_from_balance(
+                    initiator_balance,
+                    ctx.accounts.stake_mint.key(),
+                    ctx.accounts.session.as_mut(),
+                    entry_amount,
+                )?;
+            }
+            None => {
+                let initiator_token_account = ctx
+                    .accounts
+                    .initiator_token_account
+                    .as_ref()
+                    .ok_or(error!(CustomError::InvalidTokenAccount))?;
+
+                deposit_stake(
+                    &ctx.accounts.initiator.to_account_info(),
+                    &initiator_token_account.to_account_info(),
+                    &vault.to_account_info(),
+                    &ctx.accounts.stake_mint,
+                    ctx.accounts.session.as_mut(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    &ctx.accounts.token_program.to_account_info(),
+                    entry_amount,
+                )?;
+            }
+        }
+
+        vault.reload()?;
+        game_state.validate_escrow(vault.amount, ctx.accounts.shared_escrow.as_deref())?;
 
         emit!(GameCreated {
             game_id: ctx.accounts.game_state.game_id,
@@ -83,134 +311,254 @@ pub mod zero_sum {
             initiator: *ctx.accounts.initiator.key,
             prediction,
             initial_price,
-            entry_amount: ENTRY_AMOUNT_USDC,
+            stake_mint: ctx.accounts.game_state.stake_mint,
+            entry_amount: ctx.accounts.game_state.entry_amount,
+            start_at,
+            anchor_price_at_join,
+            timeout_policy,
+            increase_threshold_bps,
+            decrease_threshold_bps,
+            win_condition,
             timestamp: current_time,
         });
 
         Ok(())
     }
 
-    /// Allows a second player (challenger) to join an open game.
+    /// Captures the initial price of a scheduled game.
     ///
-    /// The challenger is automatically assigned the *opposite* prediction to the initiator.
+    /// Anyone can call this from the game's `start_at` until `START_GAME_WINDOW_SECONDS`
+    /// after it, so the initial price is always taken close to the scheduled time. If a
+    /// challenger has already joined, the game's timeout runs from now.
     ///
     /// This function:
-    /// - Validates game is joinable
-    /// - Ensures challenger is not the same as initiator
-    /// - Marks the game as started
-    pub fn join_game(ctx: Context<JoinGame>, game_id: u64, initiator: Pubkey) -> Result<()> {
+    /// - Ensures the game is scheduled, still open and within its start window
+    /// - Sets the initial price from the game's feed
+    pub fn start_game(ctx: Context<StartGame>, game_id: u64, initiator: Pubkey) -> Result<()> {
         let game_state = &mut ctx.accounts.game_state;
-        let challenger = &ctx.accounts.challenger;
-        let challenger_key = challenger.key();
         let current_time = Clock::get()?.unix_timestamp;
 
-        game_state.validate_join(game_id, challenger_key, initiator)?;
+        require!(!game_state.is_closed(), CustomError::GameAlreadyEnded);
 
-        // Get price data from Chainlink
-        let current_price = get_chainlink_price(
+        let start_at = game_state
+            .start_at
+            .ok_or(error!(CustomError::GameNotScheduled))?;
+
+        require!(current_time >= start_at, CustomError::StartTimeNotReached);
+        require!(
+            !game_state.is_start_missed(current_time),
+            CustomError::StartWindowMissed
+        );
+
+        // Take the initial price from the game's price sources
+        let initial_price = get_price(
             &ctx.accounts.chainlink_program,
             &ctx.accounts.chainlink_feed,
+            game_state.price_sources.as_ref(),
+            ctx.remaining_accounts,
             current_time,
         )?;
 
-        let (threshold_exceeded, _, _) = has_price_moved_by_percentage(
-            game_state.initial_price,
-            current_price,
-            MAX_JOIN_PRICE_MOVEMENT,
-        )?;
+        game_state.initial_price = initial_price;
+        game_state.start_at = None;
 
-        require!(!threshold_exceeded, CustomError::ExcessivePriceVolatility);
+        if game_state.challenger.is_some() {
+            game_state.started_at = Some(current_time);
+        }
 
-        game_state.challenger = Some(challenger_key);
-        game_state.started_at = Some(current_time);
-        game_state.status = GameStatus::Active;
+        emit!(GameStarted {
+            game_id,
+            initiator,
+            initial_price,
+            timestamp: current_time,
+        });
 
-        // Transfer entry amount into escrow / vault account
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = TransferChecked {
-            from: ctx.accounts.challenger_token_account.to_account_info(),
-            to: ctx.accounts.vault.to_account_info(),
-            mint: ctx.accounts.usdc_mint.to_account_info(),
-            authority: ctx.accounts.challenger.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        Ok(())
+    }
+
+    /// Re-anchors an open game to the current price.
+    ///
+    /// Lets the initiator keep a pending game competitive after the market has moved,
+    /// without withdrawing and recreating it. Each refresh bumps the game's refresh nonce,
+    /// so joins signed against the previous price fail instead of landing at the new one.
+    ///
+    /// This function:
+    /// - Validates the caller is the initiator and no challenger has joined
+    /// - Resets the initial price from the game's feed
+    /// - Resets the creation time to now
+    /// - Bumps the refresh nonce
+    pub fn refresh_game(ctx: Context<RefreshGame>, game_id: u64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let current_time = Clock::get()?.unix_timestamp;
 
-        token::transfer_checked(
-            cpi_ctx,
-            game_state.entry_amount,
-            ctx.accounts.usdc_mint.decimals,
+        game_state.validate_refresh(game_id, ctx.accounts.initiator.key())?;
+
+        // Re-anchor to the current price from the game's price sources
+        let initial_price = get_price(
+            &ctx.accounts.chainlink_program,
+            &ctx.accounts.chainlink_feed,
+            game_state.price_sources.as_ref(),
+            ctx.remaining_accounts,
+            current_time,
         )?;
 
-        let challenger_prediction = game_state.get_challenger_prediction();
+        game_state.initial_price = initial_price;
+        game_state.created_at = current_time;
+        game_state.refresh_nonce = game_state
+            .refresh_nonce
+            .checked_add(1)
+            .ok_or(error!(CustomError::Overflow))?;
 
-        emit!(GameJoined {
+        emit!(GameRefreshed {
             game_id,
-            status: GameStatus::Active,
-            challenger: challenger_key,
-            challenger_prediction,
-            timestamp: current_time,
+            initiator: game_state.initiator,
+            initial_price,
+            refresh_nonce: game_state.refresh_nonce,
+            created_at: current_time,
         });
+
         Ok(())
     }
 
-    /// Closes the game and determines outcome based on price movement.
+    /// Allows a second player (challenger) to join an open game.
     ///
-    /// Called by the initiator once the game is ready to be closed (price condition met).
+    /// The challenger is automatically assigned the *opposite* prediction to the initiator.
+    /// `expected_refresh_nonce` must match the game's refresh nonce, so a refresh can't
+    /// move the game out from under the join. `expected_initial_price` and
+    /// `max_adverse_drift_bps` optionally protect the challenger from the game's initial
+    /// price changing, or the price moving against their side, before the transaction
+    /// lands. The stake comes from the challenger's token account, or from their internal
+    /// balance when `challenger_balance` is passed. Only games whose initiator staked from
+    /// their balance can be joined from one, so the stakes never leave the balance vault.
     ///
     /// This function:
-    /// - Validates that the game is active and not already closed
-    /// - Marks the game as closed
-    /// - Handles payout logic based on outcome
-    pub fn close_game(ctx: Context<CloseGame>, game_id: u64, initiator: Pubkey) -> Result<()> {
+    /// - Validates game is joinable and hasn't been refreshed since the challenger read it
+    /// - Ensures neither the challenger nor the signing authority is the initiator
+    /// - Checks the challenger's optional slippage limits against the current price
+    /// - Re-anchors the initial price to the current price for games created that way
+    /// - Marks the game as started
+    /// - Records both players' current ratings for settlement
+    /// - Mints a single position token for each side to its player
+    pub fn join_game(
+        ctx: Context<JoinGame>,
+        game_id: u64,
+        initiator: Pubkey,
+        expected_refresh_nonce: u32,
+        expected_initial_price: Option<f64>,
+        max_adverse_drift_bps: Option<u16>,
+    ) -> Result<()> {
         let game_state = &mut ctx.accounts.game_state;
+        let challenger = &ctx.accounts.challenger;
+        let challenger_key = challenger.key();
         let current_time = Clock::get()?.unix_timestamp;
 
-        game_state.validate_close(game_id, ctx.accounts.winner.key(), initiator)?;
+        authorize_player(
+            ctx.accounts.authority.key,
+            &challenger_key,
+            ctx.accounts.session.as_deref(),
+            SESSION_ACTION_JOIN_GAME,
+            current_time,
+        )?;
 
-        // Get price data from Chainlink
-        let final_price = get_chainlink_price(
+        game_state.validate_join(game_id, challenger_key, initiator, current_time)?;
+        game_state.validate_refresh_nonce(expected_refresh_nonce)?;
+
+        // The initiator can't sign for the other side either, so both ratings come from
+        // different players
+        require!(
+            !game_state.is_creator(ctx.accounts.authority.key()),
+            CustomError::CannotJoinOwnGame
+        );
+
+        // Check the join against the current price from the game's price sources
+        let current_price = get_price(
             &ctx.accounts.chainlink_program,
             &ctx.accounts.chainlink_feed,
+            game_state.price_sources.as_ref(),
+            ctx.remaining_accounts,
             current_time,
         )?;
 
-        let (threshold_exceeded, direction, price_movement_percentage) =
-            has_price_moved_by_percentage(
-                game_state.initial_price,
-                final_price,
-                WIN_PRICE_THRESHOLD,
-            )?;
+        game_state.validate_join_price(current_price)?;
+        game_state.validate_challenger_slippage(
+            current_price,
+            expected_initial_price,
+            max_adverse_drift_bps,
+        )?;
+        game_state.anchor_join_price(current_price);
 
-        require!(threshold_exceeded, CustomError::ThresholdNotReached);
+        game_state.challenger = Some(challenger_key);
+        game_state.challenger_holder = game_state.challenger;
+        game_state.started_at = Some(current_time);
+        game_state.status = GameStatus::Active;
 
-        let player_one_won = (direction == 1
-            && game_state.initiator_prediction == PricePrediction::Increase)
-            || (direction == -1 && game_state.initiator_prediction == PricePrediction::Decrease);
+        // Snapshot both players' ratings so settlement doesn't depend on later games
+        let initiator_profile = &mut ctx.accounts.initiator_profile;
+        initiator_profile.initialize(
+            initiator,
+            game_state.stake_mint,
+            ctx.bumps.initiator_profile,
+        );
+        game_state.initiator_rating = initiator_profile.rating;
 
-        let winner = if player_one_won {
-            game_state.initiator
-        } else {
-            game_state.challenger.unwrap()
-        };
+        let challenger_profile = &mut ctx.accounts.challenger_profile;
+        challenger_profile.initialize(
+            challenger_key,
+            game_state.stake_mint,
+            ctx.bumps.challenger_profile,
+        );
+        game_state.challenger_rating = challenger_profile.rating;
 
-        let winning_prediction = if direction == 1 {
-            PricePrediction::Increase
-        } else {
-            PricePrediction::Decrease
-        };
+        let entry_amount = game_state.entry_amount;
+        game_state.lock_escrow(entry_amount, ctx.accounts.shared_escrow.as_deref_mut())?;
 
-        require!(
-            ctx.accounts.winner.key() == winner,
-            CustomError::NotTheWinner
-        );
+        // Stake the entry amount from the challenger's internal balance, or transfer it into
+        // escrow / vault account from their token account
+        match ctx.accounts.challenger_balance.as_mut() {
+            Some(challenger_balance) => {
+                require!(game_state.balance_escrow, CustomError::InvalidEscrow);
 
-        // Mark game as completed
-        game_state.closed_at = Some(current_time);
-        game_state.final_price = Some(final_price);
-        game_state.winning_prediction = Some(winning_prediction);
-        game_state.status = GameStatus::Complete(winning_prediction);
+                stake_from_balance(
+                    challenger_balance,
+                    ctx.accounts.stake_mint.key(),
+                    ctx.accounts.session.as_mut(),
+                    entry_amount,
+                )?;
+            }
+            None => {
+                let challenger_token_account = ctx
+                    .accounts
+                    .challenger_token_account
+                    .as_ref()
+                    .ok_or(error!(CustomError::InvalidTokenAccount))?;
+
+                deposit_stake(
+                    &ctx.accounts.challenger.to_account_info(),
+                    &challenger_token_account.to_account_info(),
+                    &ctx.accounts.vault.to_account_info(),
+                    &ctx.accounts.stake_mint,
+                    ctx.accounts.session.as_mut(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    &ctx.accounts.token_program.to_account_info(),
+                    entry_amount,
+                )?;
+            }
+        }
+
+        ctx.accounts.vault.reload()?;
+        game_state.validate_escrow(
+            ctx.accounts.vault.amount,
+            ctx.accounts.shared_escrow.as_deref(),
+        )?;
+
+        // Mint a single position token for each side and fix the supply
+        let initiator_position_mint = ctx.accounts.initiator_position_mint.key();
+        let challenger_position_mint = ctx.accounts.challenger_position_mint.key();
+
+        game_state.initiator_position_mint = Some(initiator_position_mint);
+        game_state.challenger_position_mint = Some(challenger_position_mint);
+        game_state.join_rent_payer = Some(ctx.accounts.payer.key());
 
-        // Transfer the full balance (both players' stakes) to the winner
         let seeds = &[
             b"game_state",
             initiator.as_ref(),
@@ -219,69 +567,202 @@ pub mod zero_sum {
         ];
         let signer = &[&seeds[..]];
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = TransferChecked {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.winner_token_account.to_account_info(),
-            mint: ctx.accounts.usdc_mint.to_account_info(),
-            authority: game_state.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-
-        let total_payout = game_state
-            .entry_amount
-            .checked_mul(2)
-            .ok_or(error!(CustomError::Overflow))?;
+        mint_position_tokens(
+            [
+                (
+                    ctx.accounts.initiator_position_mint.to_account_info(),
+                    ctx.accounts.initiator_position_account.to_account_info(),
+                ),
+                (
+                    ctx.accounts.challenger_position_mint.to_account_info(),
+                    ctx.accounts.challenger_position_account.to_account_info(),
+                ),
+            ],
+            &game_state.to_account_info(),
+            &ctx.accounts.position_token_program.to_account_info(),
+            signer,
+        )?;
 
-        token::transfer_checked(cpi_ctx, total_payout, ctx.accounts.usdc_mint.decimals)?;
+        let challenger_prediction = game_state.get_challenger_prediction();
 
-        emit!(GameClosed {
+        emit!(GameJoined {
             game_id,
-            status: GameStatus::Complete(winning_prediction),
-            details: GameStatusDetails::Complete {
-                winner,
-                winning_prediction,
-                price_movement_percentage,
-                final_price,
-                total_payout,
-            },
+            status: GameStatus::Active,
+            challenger: challenger_key,
+            challenger_prediction,
+            initial_price: game_state.initial_price,
+            initiator_position_mint,
+            challenger_position_mint,
             timestamp: current_time,
         });
-
         Ok(())
     }
 
-    /// Allows players to claim back their stake if the game has timed out
-    /// without reaching the price threshold.
+    /// Merges two pending games that take opposite sides of the same bet into one game.
+    ///
+    /// Anyone can call this, e.g. a keeper pairing up waiting initiators. The matched game's
+    /// initiator becomes the challenger of the first game at its initial price, so both
+    /// games' initial prices must be within the join tolerance of the current price. Games
+    /// held in different vaults of a mint with a transfer fee can't be matched, as moving
+    /// the stake would leave the first game's vault short. Games funded from internal
+    /// balances can only be matched with each other, so their stakes stay in the balance
+    /// vault. Both refresh nonces must match the ones the caller read, so neither game can
+    /// be refreshed before the match lands.
     ///
     /// This function:
-    /// - Checks if the game is active
-    /// - Verifies that the timeout period has elapsed
-    /// - Returns the entry amount to both players
-    pub fn draw_game(ctx: Context<DrawGame>, game_id: u64, initiator: Pubkey) -> Result<()> {
+    /// - Validates each game is joinable by the other's initiator and hasn't been refreshed
+    /// - Ensures neither game is a tournament match
+    /// - Ensures both share the stake mint, entry amount, feed and settings with opposite
+    ///   predictions
+    /// - Checks both initial prices against the current price
+    /// - Marks the first game as started with the matched initiator as challenger
+    /// - Records both players' current ratings for settlement
+    /// - Moves the matched game's stake into the first game's vault
+    /// - Closes the matched game and its vault, refunding their rent to whoever paid for it
+    /// - Mints a single position token for each side to its player
+    pub fn match_games(
+        ctx: Context<MatchGames>,
+        game_id: u64,
+        initiator: Pubkey,
+        matched_game_id: u64,
+        matched_initiator: Pubkey,
+        expected_refresh_nonce: u32,
+        expected_matched_refresh_nonce: u32,
+    ) -> Result<()> {
         let game_state = &mut ctx.accounts.game_state;
+        let matched_game_state = &mut ctx.accounts.matched_game_state;
         let current_time = Clock::get()?.unix_timestamp;
 
-        game_state.validate_close(game_id, ctx.accounts.player.key(), initiator)?;
-
-        // Check the game hasn't been closed already
-        require!(!game_state.is_closed(), CustomError::GameAlreadyEnded);
+        game_state.validate_join(game_id, matched_initiator, initiator, current_time)?;
+        matched_game_state.validate_join(
+            matched_game_id,
+            initiator,
+            matched_initiator,
+            current_time,
+        )?;
+        game_state.validate_refresh_nonce(expected_refresh_nonce)?;
+        matched_game_state.validate_refresh_nonce(expected_matched_refresh_nonce)?;
+        game_state.validate_match(matched_game_state)?;
 
-        // Check if timeout has elapsed
-        let start_time = game_state.started_at.unwrap();
         require!(
-            current_time
-                > start_time
-                    .checked_add(GAME_TIMEOUT_SECONDS)
-                    .ok_or(error!(CustomError::Overflow))?,
-            CustomError::GameTimeoutNotReached
+            ctx.accounts.chainlink_feed.key() == game_state.price_feed,
+            CustomError::InvalidPriceFeed
         );
 
-        // Mark game as closed
-        game_state.closed_at = Some(current_time);
-        game_state.status = GameStatus::Draw;
+        // Check the join against the current price from the game's price sources
+        let current_price = get_price(
+            &ctx.accounts.chainlink_program,
+            &ctx.accounts.chainlink_feed,
+            game_state.price_sources.as_ref(),
+            ctx.remaining_accounts,
+            current_time,
+        )?;
+
+        game_state.validate_join_price(current_price)?;
+        game_state.anchor_join_price(current_price);
+        matched_game_state.validate_join_price(current_price)?;
+
+        game_state.challenger = Some(matched_initiator);
+        game_state.challenger_holder = game_state.challenger;
+        game_state.started_at = Some(current_time);
+        game_state.status = GameStatus::Active;
+
+        // Snapshot both players' ratings so settlement doesn't depend on later games
+        let initiator_profile = &mut ctx.accounts.initiator_profile;
+        initiator_profile.initialize(
+            initiator,
+            game_state.stake_mint,
+            ctx.bumps.initiator_profile,
+        );
+        game_state.initiator_rating = initiator_profile.rating;
+
+        let challenger_profile = &mut ctx.accounts.challenger_profile;
+        challenger_profile.initialize(
+            matched_initiator,
+            game_state.stake_mint,
+            ctx.bumps.challenger_profile,
+        );
+        game_state.challenger_rating = challenger_profile.rating;
+
+        // Move the matched game's escrow over to the first game. Either game may hold its
+        // stakes in the shared escrow vault.
+        let entry_amount = game_state.entry_amount;
+        let matched_shared_escrow = ctx
+            .accounts
+            .shared_escrow
+            .as_deref_mut()
+            .filter(|_| matched_game_state.shared_escrow);
+        matched_game_state.release_escrow(entry_amount, matched_shared_escrow)?;
+
+        let shared_escrow = ctx
+            .accounts
+            .shared_escrow
+            .as_deref_mut()
+            .filter(|_| game_state.shared_escrow);
+        game_state.lock_escrow(entry_amount, shared_escrow)?;
+
+        // Games in the shared escrow vault already share their tokens
+        if ctx.accounts.matched_vault.key() != ctx.accounts.vault.key() {
+            let matched_seeds = &[
+                b"game_state",
+                matched_initiator.as_ref(),
+                &matched_game_id.to_le_bytes(),
+                &[matched_game_state.bump],
+            ];
+            let matched_signer = &[&matched_seeds[..]];
+
+            let (vault_authority, vault_seeds) = vault_authority_and_signer(
+                matched_game_state,
+                ctx.accounts.shared_escrow.as_ref(),
+                &ctx.accounts.config,
+            )?;
+            let vault_seeds: Vec<&[u8]> = vault_seeds.iter().map(Vec::as_slice).collect();
+            let vault_signer = &[&vault_seeds[..]];
+
+            transfer_from_vault(
+                &ctx.accounts.matched_vault.to_account_info(),
+                &ctx.accounts.vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &ctx.accounts.token_program.to_account_info(),
+                vault_signer,
+                entry_amount,
+            )?;
+
+            if matched_game_state.shared_escrow {
+                ctx.accounts.matched_vault.reload()?;
+                matched_game_state.validate_escrow(
+                    ctx.accounts.matched_vault.amount,
+                    ctx.accounts.shared_escrow.as_deref(),
+                )?;
+            } else {
+                close_empty_vault(
+                    &mut ctx.accounts.matched_vault,
+                    &ctx.accounts.matched_rent_payer.to_account_info(),
+                    &matched_game_state.to_account_info(),
+                    &ctx.accounts.token_program.to_account_info(),
+                    matched_signer,
+                )?;
+            }
+        }
+
+        ctx.accounts.vault.reload()?;
+        game_state.validate_escrow(
+            ctx.accounts.vault.amount,
+            ctx.accounts
+                .shared_escrow
+                .as_deref()
+                .filter(|_| game_state.shared_escrow),
+        )?;
+
+        // Mint a single position token for each side and fix the supply
+        let initiator_position_mint = ctx.accounts.initiator_position_mint.key();
+        let challenger_position_mint = ctx.accounts.challenger_position_mint.key();
+
+        game_state.initiator_position_mint = Some(initiator_position_mint);
+        game_state.challenger_position_mint = Some(challenger_position_mint);
+        game_state.join_rent_payer = Some(ctx.accounts.payer.key());
 
-        // Return the entry amounts to players
         let seeds = &[
             b"game_state",
             initiator.as_ref(),
@@ -290,97 +771,2557 @@ pub mod zero_sum {
         ];
         let signer = &[&seeds[..]];
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-
-        // Return initiator's stake
-        let cpi_accounts_initiator = TransferChecked {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.initiator_token_account.to_account_info(),
-            mint: ctx.accounts.usdc_mint.to_account_info(),
-            authority: game_state.to_account_info(),
-        };
-        let cpi_ctx_initiator =
-            CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts_initiator, signer);
-
-        token::transfer_checked(
-            cpi_ctx_initiator,
-            game_state.entry_amount,
-            ctx.accounts.usdc_mint.decimals,
+        mint_position_tokens(
+            [
+                (
+                    ctx.accounts.initiator_position_mint.to_account_info(),
+                    ctx.accounts.initiator_position_account.to_account_info(),
+                ),
+                (
+                    ctx.accounts.challenger_position_mint.to_account_info(),
+                    ctx.accounts.challenger_position_account.to_account_info(),
+                ),
+            ],
+            &game_state.to_account_info(),
+            &ctx.accounts.position_token_program.to_account_info(),
+            signer,
         )?;
 
-        // Return challenger's stake
-        let cpi_accounts_challenger = TransferChecked {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.challenger_token_account.to_account_info(),
-            mint: ctx.accounts.usdc_mint.to_account_info(),
-            authority: game_state.to_account_info(),
-        };
-        let cpi_ctx_challenger =
-            CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts_challenger, signer);
-
-        token::transfer_checked(
-            cpi_ctx_challenger,
-            game_state.entry_amount,
-            ctx.accounts.usdc_mint.decimals,
-        )?;
+        emit!(GamesMatched {
+            game_id,
+            initiator,
+            matched_game_id,
+            matched_initiator,
+            timestamp: current_time,
+        });
 
-        emit!(GameClosed {
+        emit!(GameJoined {
             game_id,
-            status: GameStatus::Draw,
-            details: GameStatusDetails::None,
+            status: GameStatus::Active,
+            challenger: matched_initiator,
+            challenger_prediction: game_state.get_challenger_prediction(),
+            initial_price: game_state.initial_price,
+            initiator_position_mint,
+            challenger_position_mint,
             timestamp: current_time,
         });
 
         Ok(())
     }
 
-    /// Cancels a game and allows the initiator to withdraw their stake.
+    /// Closes the game and determines outcome based on price movement.
     ///
-    /// Only allowed if no challenger has joined yet.
+    /// Called by the current holder of the winning side, who must also hold its position
+    /// token, once the game is ready to be closed (price condition met). Games with a
+    /// sustained win condition are settled with `round_ids`, consecutive oracle rounds
+    /// ending within `MAX_ROUND_PROOF_AGE_SECONDS` that prove it held.
     ///
     /// This function:
-    /// - Ensures the game is still joinable and hasn't closed
-    /// - Returns the entry amount to the initiator
-    /// - Marks the game as cancelled
-    pub fn cancel_game(ctx: Context<CancelGame>, game_id: u64) -> Result<()> {
+    /// - Validates that the game is active and not already closed
+    /// - Marks the game as closed
+    /// - Handles payout logic based on outcome, crediting the winner's internal balance for
+    ///   games funded from balances
+    /// - Burns the winning position token, and the losing one if its account is passed
+    /// - Records the result in the profiles of the players who started the game
+    /// - Takes the protocol fee, paying the season's share into its prize pool
+    /// - Credits each player's referrer, if registered, with a share of the fee
+    /// - Awards season points to the winner if a season is running
+    /// - Releases the house pool's stake from its exposure if the pool played
+    /// - Closes the game, unless it is a tournament match
+    pub fn close_game(
+        ctx: Context<CloseGame>,
+        game_id: u64,
+        initiator: Pubkey,
+        round_ids: Vec<u32>,
+    ) -> Result<()> {
         let game_state = &mut ctx.accounts.game_state;
-        let initiator = &ctx.accounts.initiator;
-        let initiator_key = initiator.key();
         let current_time = Clock::get()?.unix_timestamp;
 
-        game_state.validate_withdraw(game_id, initiator_key)?;
+        // The house pool's winnings can only be paid into its own vault, so anyone may
+        // redeem them for it
+        let house_pool_won = game_state.house_pool == Some(ctx.accounts.winner.key());
 
-        game_state.closed_at = Some(current_time);
-        game_state.status = GameStatus::Cancelled;
+        if !house_pool_won {
+            authorize_player(
+                ctx.accounts.authority.key,
+                ctx.accounts.winner.key,
+                ctx.accounts.session.as_deref(),
+                SESSION_ACTION_CLOSE_GAME,
+                current_time,
+            )?;
+        }
 
-        // Return the entry amount to initiator
-        let seeds = &[
-            b"game_state",
-            initiator_key.as_ref(),
-            &game_id.to_le_bytes(),
-            &[game_state.bump],
-        ];
-        let signer = &[&seeds[..]];
+        game_state.validate_close(game_id, ctx.accounts.winner.key(), initiator)?;
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts = TransferChecked {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.initiator_token_account.to_account_info(),
-            mint: ctx.accounts.usdc_mint.to_account_info(),
-            authority: game_state.to_account_info(),
+        // Take the final price from the game's price sources. Sustained win conditions are
+        // tested against the proven rounds rather than the latest price alone.
+        let final_price = if game_state.win_condition == WinCondition::Instant {
+            get_price(
+                &ctx.accounts.chainlink_program,
+                &ctx.accounts.chainlink_feed,
+                game_state.price_sources.as_ref(),
+                ctx.remaining_accounts,
+                current_time,
+            )?
+        } else {
+            let rounds = get_chainlink_round_history(
+                &ctx.accounts.chainlink_program,
+                &ctx.accounts.chainlink_feed,
+                &round_ids,
+            )?;
+            game_state.get_sustained_price(&rounds, current_time)?
         };
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer_checked(
-            cpi_ctx,
-            game_state.entry_amount,
-            ctx.accounts.usdc_mint.decimals,
-        )?;
+        let (winning_prediction, price_movement_percentage) =
+            game_state.get_winning_prediction(final_price)?;
 
-        emit!(GameClosed {
-            game_id,
-            status: GameStatus::Cancelled,
+        let winner = ctx.accounts.winner.key();
+
+        require!(
+            game_state.get_player_prediction(winner)? == winning_prediction,
+            CustomError::NotTheWinner
+        );
+        require!(
+            ctx.accounts.position_mint.key() == game_state.get_player_position_mint(winner)?,
+            CustomError::InvalidPositionToken
+        );
+
+        require!(
+            ctx.accounts.loser_position_mint.key()
+                == game_state.get_opposing_position_mint(ctx.accounts.position_mint.key())?,
+            CustomError::InvalidPositionToken
+        );
+
+        // Mark game as completed
+        game_state.closed_at = Some(current_time);
+        game_state.final_price = Some(final_price);
+        game_state.winning_prediction = Some(winning_prediction);
+        game_state.status = GameStatus::Complete(winning_prediction);
+
+        // Release the house pool's stake from its exposure, whether it won or lost
+        if let Some(house_pool_key) = game_state.house_pool {
+            let house_pool = ctx
+                .accounts
+                .house_pool
+                .as_mut()
+                .ok_or(error!(CustomError::InvalidHousePool))?;
+
+            require!(
+                house_pool.key() == house_pool_key,
+                CustomError::InvalidHousePool
+            );
+
+            house_pool.release_exposure(
+                game_state.price_feed,
+                game_state.get_challenger_prediction(),
+                game_state.entry_amount,
+            )?;
+        }
+
+        // Rate the result using the ratings both sides had when the game started
+        let initiator_won = game_state.is_initiator(winner);
+        let (initiator_rating_delta, challenger_rating_delta) =
+            game_state.get_rating_deltas(if initiator_won { 1.0 } else { 0.0 });
+        let (winner_rating_delta, loser_rating_delta) = if initiator_won {
+            (initiator_rating_delta, challenger_rating_delta)
+        } else {
+            (challenger_rating_delta, initiator_rating_delta)
+        };
+
+        // Count the win towards the stake mint's current season while it is running
+        let season_key = get_running_season(
+            &ctx.accounts.allowed_mint,
+            ctx.accounts.season.as_deref(),
+            current_time,
+        )?;
+        let mut season_points = 0;
+
+        // Take the protocol fee from the pot (both players' stakes). Each player with a
+        // registered referrer passes their referrer a share of their half of the fee.
+        let config = &ctx.accounts.config;
+        let total_pot = game_state
+            .entry_amount
+            .checked_mul(2)
+            .ok_or(error!(CustomError::Overflow))?;
+        let protocol_fee = calculate_bps_share(total_pot, config.protocol_fee_bps)?;
+        let total_payout = total_pot - protocol_fee;
+        let referral_fee = calculate_bps_share(protocol_fee / 2, config.referral_share_bps)?;
+        let initiator_referral_fee = if ctx.accounts.initiator_referral.data_is_empty() {
+            0
+        } else {
+            referral_fee
+        };
+        let challenger_referral_fee = if ctx.accounts.challenger_referral.data_is_empty() {
+            0
+        } else {
+            referral_fee
+        };
+        let mut treasury_fee = protocol_fee - initiator_referral_fee - challenger_referral_fee;
+
+        // Update lifetime statistics of the players who started the game, whoever holds
+        // their sides now
+        let initiator_profile = &mut ctx.accounts.initiator_profile;
+        initiator_profile.initialize(
+            game_state.initiator,
+            game_state.stake_mint,
+            ctx.bumps.initiator_profile,
+        );
+        initiator_profile.accrue_referral_rewards(initiator_referral_fee)?;
+
+        let challenger_profile = &mut ctx.accounts.challenger_profile;
+        challenger_profile.initialize(
+            game_state.challenger.unwrap_or_default(),
+            game_state.stake_mint,
+            ctx.bumps.challenger_profile,
+        );
+        challenger_profile.accrue_referral_rewards(challenger_referral_fee)?;
+
+        let (winner_profile, loser_profile) = if initiator_won {
+            (initiator_profile, challenger_profile)
+        } else {
+            (challenger_profile, initiator_profile)
+        };
+        winner_profile.record_win(game_state.entry_amount)?;
+        winner_profile.apply_rating_delta(winner_rating_delta);
+        if let Some(season_key) = season_key {
+            season_points = winner_profile.add_season_points(season_key, SEASON_POINTS_WIN)?;
+        }
+        let winning_player = winner_profile.player;
+        loser_profile.record_loss(game_state.entry_amount)?;
+        loser_profile.apply_rating_delta(loser_rating_delta);
+
+        let seeds = &[
+            b"game_state",
+            initiator.as_ref(),
+            &game_id.to_le_bytes(),
+            &[game_state.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let (vault_authority, vault_seeds) = vault_authority_and_signer(
+            game_state,
+            ctx.accounts.shared_escrow.as_ref(),
+            &ctx.accounts.config,
+        )?;
+        let vault_seeds: Vec<&[u8]> = vault_seeds.iter().map(Vec::as_slice).collect();
+        let vault_signer = &[&vault_seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let vault = ctx.accounts.vault.to_account_info();
+
+        // Hold referral rewards until each referrer withdraws them
+        let total_referral_fee = initiator_referral_fee + challenger_referral_fee;
+
+        if total_referral_fee > 0 {
+            transfer_from_vault(
+                &vault,
+                &ctx.accounts.referral_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                total_referral_fee,
+            )?;
+        }
+
+        // Pay the season's share of the fee into its prize pool while it is running
+        if let Some(season) = ctx
+            .accounts
+            .season
+            .as_mut()
+            .filter(|_| season_key.is_some())
+        {
+            let season_vault = ctx
+                .accounts
+                .season_vault
+                .as_ref()
+                .ok_or(error!(CustomError::InvalidTokenAccount))?;
+
+            require!(
+                season_vault.key() == season.vault,
+                CustomError::InvalidTokenAccount
+            );
+
+            let season_fee = calculate_bps_share(protocol_fee, config.season_share_bps)?;
+            treasury_fee -= season_fee;
+
+            season.prize_pool = season
+                .prize_pool
+                .checked_add(season_fee)
+                .ok_or(error!(CustomError::Overflow))?;
+            season.record_points(winning_player, season_points);
+
+            transfer_from_vault(
+                &vault,
+                &season_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                season_fee,
+            )?;
+        }
+
+        transfer_from_vault(
+            &vault,
+            &ctx.accounts.treasury_token_account.to_account_info(),
+            &ctx.accounts.stake_mint,
+            &vault_authority,
+            &cpi_program,
+            vault_signer,
+            treasury_fee,
+        )?;
+
+        // Transfer the rest of the pot to the winner. Games funded from balances credit it to
+        // the winner's balance, leaving it in the balance vault. SOL stakes are paid out as
+        // lamports to the winner's wallet, also when a session key settles for them. The
+        // house pool keeps its winnings wrapped in its vault.
+        if game_state.balance_escrow {
+            credit_balance(
+                ctx.accounts.winner_balance.as_deref_mut(),
+                ctx.bumps.winner_balance,
+                winner,
+                game_state.stake_mint,
+                total_payout,
+            )?;
+        } else if is_native_mint(&game_state.stake_mint) && !house_pool_won {
+            unwrap_sol(
+                &vault,
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                vault_signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.winner.to_account_info(), total_payout)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &vault,
+                &ctx.accounts.winner_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                total_payout,
+            )?;
+        }
+
+        // The whole pot has left escrow
+        game_state.release_escrow(total_pot, ctx.accounts.shared_escrow.as_deref_mut())?;
+        ctx.accounts.vault.reload()?;
+        game_state.validate_escrow(
+            ctx.accounts.vault.amount,
+            ctx.accounts.shared_escrow.as_deref(),
+        )?;
+
+        // Close the emptied game vault, refunding its rent to whoever paid for it
+        if game_state.has_own_vault() {
+            close_empty_vault(
+                &mut ctx.accounts.vault,
+                &ctx.accounts.rent_payer.to_account_info(),
+                &game_state.to_account_info(),
+                &ctx.accounts.token_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        // Burn the redeemed position token as its permanent delegate and close its mint
+        let join_rent_payer = ctx.accounts.join_rent_payer.to_account_info();
+        let position_token_program = ctx.accounts.position_token_program.to_account_info();
+        burn_position_token(
+            &ctx.accounts.position_mint.to_account_info(),
+            &ctx.accounts.winner_position_account.to_account_info(),
+            &game_state.to_account_info(),
+            &join_rent_payer,
+            &position_token_program,
+            signer,
+        )?;
+
+        // Burn the losing position token too if its account was passed. Its mint can only
+        // be closed once the token is gone.
+        if let Some(loser_position_account) = ctx.accounts.loser_position_account.as_ref() {
+            burn_position_token(
+                &ctx.accounts.loser_position_mint.to_account_info(),
+                &loser_position_account.to_account_info(),
+                &game_state.to_account_info(),
+                &join_rent_payer,
+                &position_token_program,
+                signer,
+            )?;
+        } else if ctx.accounts.loser_position_mint.supply == 0 {
+            close_token_account(
+                &ctx.accounts.loser_position_mint.to_account_info(),
+                &join_rent_payer,
+                &game_state.to_account_info(),
+                &position_token_program,
+                signer,
+            )?;
+        }
+
+        // Close the winner's emptied position account, when the winner signed for it
+        // themselves or it belongs to the house pool
+        if let Some(house_pool) = ctx.accounts.house_pool.as_ref().filter(|_| house_pool_won) {
+            let house_pool_seeds = &[
+                b"house_pool".as_ref(),
+                house_pool.stake_mint.as_ref(),
+                &[house_pool.bump],
+            ];
+            close_token_account(
+                &ctx.accounts.winner_position_account.to_account_info(),
+                &join_rent_payer,
+                &house_pool.to_account_info(),
+                &position_token_program,
+                &[&house_pool_seeds[..]],
+            )?;
+        } else if ctx.accounts.authority.key() == winner {
+            close_token_account(
+                &ctx.accounts.winner_position_account.to_account_info(),
+                &ctx.accounts.winner.to_account_info(),
+                &ctx.accounts.winner.to_account_info(),
+                &position_token_program,
+                &[],
+            )?;
+        }
+
+        emit!(PositionRedeemed {
+            game_id,
+            prediction: winning_prediction,
+            holder: winner,
+            amount: total_payout,
+            timestamp: current_time,
+        });
+
+        emit!(GameClosed {
+            game_id,
+            status: GameStatus::Complete(winning_prediction),
+            details: GameStatusDetails::Complete {
+                winner,
+                winning_prediction,
+                price_movement_percentage,
+                final_price,
+                total_payout,
+                protocol_fee,
+            },
+            initiator_rating_delta,
+            challenger_rating_delta,
+            timestamp: current_time,
+        });
+
+        // Both sides are settled, so close the game and refund its rent. Tournament games
+        // stay open until their result is reported.
+        if !game_state.is_tournament_game() {
+            game_state.close(ctx.accounts.rent_payer.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Transfers a player's position token for an active game to a new holder.
+    ///
+    /// Both the current holder and the new holder sign, so the side can be sold
+    /// atomically for an agreed USDC price. A price of zero assigns the side for free.
+    /// Position tokens moved outside the program are registered to their new holder
+    /// with `claim_position`.
+    ///
+    /// This function:
+    /// - Validates the game is active and the signer holds one of its sides
+    /// - Pays the agreed price from the new holder to the current holder
+    /// - Moves the position token to the new holder
+    /// - Reassigns the side to the new holder
+    pub fn transfer_position(
+        ctx: Context<TransferPosition>,
+        game_id: u64,
+        initiator: Pubkey,
+        price: u64,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let holder_key = ctx.accounts.holder.key();
+        let new_holder_key = ctx.accounts.new_holder.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        game_state.validate_transfer(game_id, holder_key, new_holder_key, initiator)?;
+
+        require!(
+            ctx.accounts.position_mint.key() == game_state.get_player_position_mint(holder_key)?,
+            CustomError::InvalidPositionToken
+        );
+
+        let prediction = game_state.get_player_prediction(holder_key)?;
+        game_state.set_holder(holder_key, new_holder_key);
+
+        // Pay the agreed price to the current holder, straight from wallet to wallet for
+        // SOL stakes
+        if price > 0 && is_native_mint(&game_state.stake_mint) {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.new_holder.to_account_info(),
+                to: ctx.accounts.holder.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+
+            system_program::transfer(cpi_ctx, price)?;
+        } else if price > 0 {
+            let cpi_accounts = TransferChecked {
+                from: ctx.accounts.new_holder_token_account.to_account_info(),
+                to: ctx.accounts.holder_token_account.to_account_info(),
+                mint: ctx.accounts.stake_mint.to_account_info(),
+                authority: ctx.accounts.new_holder.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+            token_interface::transfer_checked(cpi_ctx, price, ctx.accounts.stake_mint.decimals)?;
+        }
+
+        // Hand the position token over to the new holder
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.holder_position_account.to_account_info(),
+            to: ctx.accounts.new_holder_position_account.to_account_info(),
+            mint: ctx.accounts.position_mint.to_account_info(),
+            authority: ctx.accounts.holder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.position_token_program.to_account_info(),
+            cpi_accounts,
+        );
+
+        token_interface::transfer_checked(cpi_ctx, 1, ctx.accounts.position_mint.decimals)?;
+
+        emit!(PositionTransferred {
+            game_id,
+            prediction,
+            from: holder_key,
+            to: new_holder_key,
+            price,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Registers the owner of a position token as the holder of its side.
+    ///
+    /// Position tokens can be traded outside the program, e.g. on any SPL-compatible
+    /// venue. Whoever ends up with the token claims the side before settling it, subject
+    /// to the same rules as `transfer_position`.
+    ///
+    /// This function:
+    /// - Validates the game is active and the position token belongs to it
+    /// - Ensures the claimant doesn't already hold a side of the game
+    /// - Reassigns the side from its recorded holder to the claimant
+    pub fn claim_position(
+        ctx: Context<ClaimPosition>,
+        game_id: u64,
+        initiator: Pubkey,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let claimant_key = ctx.accounts.claimant.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let position_mint = ctx.accounts.position_mint.key();
+        let holder_key = game_state.get_position_holder(position_mint)?;
+
+        game_state.validate_transfer(game_id, holder_key, claimant_key, initiator)?;
+
+        let prediction = game_state.get_position_prediction(position_mint)?;
+        game_state.set_holder(holder_key, claimant_key);
+
+        emit!(PositionTransferred {
+            game_id,
+            prediction,
+            from: holder_key,
+            to: claimant_key,
+            price: 0,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Allows players to claim back their stakes if the game has timed out without
+    /// reaching the price threshold.
+    ///
+    /// Either side's current holder settles both sides at once, including after
+    /// `declare_oracle_outage` has drawn the game early. Each side's share of the pot is
+    /// paid to whoever holds it. Games whose timeout policy splits the pot split it by the
+    /// current price from the game's price sources, passed as remaining accounts as in
+    /// `close_game`, and pay the protocol fee on an uneven split as `close_game` does. A
+    /// price unchanged since the game started refunds both stakes free of fees. Scheduled games that missed their
+    /// start window never took an initial price, and games drawn by an oracle outage are no
+    /// contest, so in both cases the stakes are refunded and neither player's record changes.
+    ///
+    /// This function:
+    /// - Checks if the game is active, or already drawn by an oracle outage
+    /// - Verifies that the timeout period has elapsed, or the start window was missed
+    /// - Takes the protocol fee from each side's share of an unevenly split pot, paying the season's
+    ///   share into its prize pool and crediting each player's referrer, if registered
+    /// - Pays out each side's share of the pot to its holder, crediting their internal
+    ///   balances for games funded from balances
+    /// - Burns both position tokens from whichever accounts hold them and closes their mints
+    /// - Records the outcome, each side's payout less its stake and both players' rating
+    ///   changes in their player profiles, if the game was played. A side given the whole
+    ///   pot wins, a side given none of it loses, and any other split is a draw.
+    /// - Releases the house pool's stake from its exposure if the pool played
+    /// - Closes the game, unless it is a tournament match
+    pub fn draw_game(ctx: Context<DrawGame>, game_id: u64, initiator: Pubkey) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let player_key = ctx.accounts.player.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // The house pool's stake can only be returned to its own vault, so anyone may
+        // settle its games for it
+        let house_pool_settling = game_state.house_pool == Some(player_key);
+
+        if !house_pool_settling {
+            authorize_player(
+                ctx.accounts.authority.key,
+                &player_key,
+                ctx.accounts.session.as_deref(),
+                SESSION_ACTION_DRAW_GAME,
+                current_time,
+            )?;
+        }
+
+        // Games drawn by an oracle outage are already closed and refund both stakes
+        let drawn_by_outage = game_state.status == GameStatus::Draw;
+
+        // Games that never started, or were cut short by an outage, are no contest, so only
+        // played draws are recorded
+        let played = !game_state.is_awaiting_start() && !drawn_by_outage;
+
+        if drawn_by_outage {
+            require!(game_state.is_player(player_key), CustomError::NotAuthorized);
+        } else {
+            game_state.validate_draw(game_id, player_key, initiator, current_time)?;
+
+            // Mark game as closed
+            game_state.closed_at = Some(current_time);
+            game_state.status = GameStatus::Draw;
+
+            // Take the price from the game's price sources to split the pot by, unless both
+            // stakes are refunded
+            if game_state.timeout_policy != TimeoutPolicy::Refund && played {
+                let (Some(chainlink_program), Some(chainlink_feed)) = (
+                    ctx.accounts.chainlink_program.as_ref(),
+                    ctx.accounts.chainlink_feed.as_ref(),
+                ) else {
+                    return err!(CustomError::InvalidPriceFeed);
+                };
+
+                let final_price = get_price(
+                    &chainlink_program.to_account_info(),
+                    &chainlink_feed.to_account_info(),
+                    game_state.price_sources.as_ref(),
+                    ctx.remaining_accounts,
+                    current_time,
+                )?;
+                game_state.final_price = Some(final_price);
+            }
+        }
+
+        // Each side scores its share of the pot, so an even split is half a point each
+        let (initiator_rating_delta, challenger_rating_delta) = if played {
+            game_state.get_rating_deltas(game_state.get_timeout_score())
+        } else {
+            (0, 0)
+        };
+
+        // Split the pot. A pot split unevenly by price settles the game by price, so each
+        // side pays the protocol fee on its share, and each player with a registered referrer
+        // passes their referrer a share of their side's fee. An even split is a refund.
+        let config = &ctx.accounts.config;
+        let initiator_share = game_state.get_timeout_payout(game_state.initiator_prediction)?;
+        let challenger_share =
+            game_state.get_timeout_payout(game_state.get_challenger_prediction())?;
+        let total_pot = initiator_share
+            .checked_add(challenger_share)
+            .ok_or(error!(CustomError::Overflow))?;
+        let (initiator_fee, challenger_fee) = if initiator_share != game_state.entry_amount {
+            (
+                calculate_bps_share(initiator_share, config.protocol_fee_bps)?,
+                calculate_bps_share(challenger_share, config.protocol_fee_bps)?,
+            )
+        } else {
+            (0, 0)
+        };
+        let protocol_fee = initiator_fee + challenger_fee;
+        let initiator_payout = initiator_share - initiator_fee;
+        let challenger_payout = challenger_share - challenger_fee;
+        let initiator_referral_fee = if ctx.accounts.initiator_referral.data_is_empty() {
+            0
+        } else {
+            calculate_bps_share(initiator_fee, config.referral_share_bps)?
+        };
+        let challenger_referral_fee = if ctx.accounts.challenger_referral.data_is_empty() {
+            0
+        } else {
+            calculate_bps_share(challenger_fee, config.referral_share_bps)?
+        };
+        let mut treasury_fee = protocol_fee - initiator_referral_fee - challenger_referral_fee;
+
+        // The season's share of the fee goes to the stake mint's current season while it is
+        // running
+        let season_key = get_running_season(
+            &ctx.accounts.allowed_mint,
+            ctx.accounts.season.as_deref(),
+            current_time,
+        )?;
+
+        if !drawn_by_outage {
+            let details = match game_state.final_price {
+                Some(final_price) => GameStatusDetails::Timeout {
+                    timeout_policy: game_state.timeout_policy,
+                    final_price,
+                    initiator_payout,
+                    challenger_payout,
+                    protocol_fee,
+                },
+                None => GameStatusDetails::None,
+            };
+
+            emit!(GameClosed {
+                game_id,
+                status: GameStatus::Draw,
+                details,
+                initiator_rating_delta,
+                challenger_rating_delta,
+                timestamp: current_time,
+            });
+        }
+
+        // Release the house pool's stake from its exposure as it is returned
+        if let Some(house_pool_key) = game_state.house_pool {
+            let house_pool = ctx
+                .accounts
+                .house_pool
+                .as_mut()
+                .ok_or(error!(CustomError::InvalidHousePool))?;
+
+            require!(
+                house_pool.key() == house_pool_key,
+                CustomError::InvalidHousePool
+            );
+
+            house_pool.release_exposure(
+                game_state.price_feed,
+                game_state.get_challenger_prediction(),
+                game_state.entry_amount,
+            )?;
+        }
+
+        // Update both players' lifetime statistics
+        let challenger_key = game_state
+            .challenger
+            .ok_or(error!(CustomError::GameNotActive))?;
+
+        let initiator_profile = &mut ctx.accounts.initiator_profile;
+        initiator_profile.initialize(
+            initiator,
+            game_state.stake_mint,
+            ctx.bumps.initiator_profile,
+        );
+        initiator_profile.accrue_referral_rewards(initiator_referral_fee)?;
+        if played {
+            initiator_profile.record_timeout(
+                game_state.entry_amount,
+                initiator_share,
+                initiator_payout,
+            )?;
+            initiator_profile.apply_rating_delta(initiator_rating_delta);
+        }
+
+        let challenger_profile = &mut ctx.accounts.challenger_profile;
+        challenger_profile.initialize(
+            challenger_key,
+            game_state.stake_mint,
+            ctx.bumps.challenger_profile,
+        );
+        challenger_profile.accrue_referral_rewards(challenger_referral_fee)?;
+        if played {
+            challenger_profile.record_timeout(
+                game_state.entry_amount,
+                challenger_share,
+                challenger_payout,
+            )?;
+            challenger_profile.apply_rating_delta(challenger_rating_delta);
+        }
+
+        let seeds = &[
+            b"game_state",
+            initiator.as_ref(),
+            &game_id.to_le_bytes(),
+            &[game_state.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let (vault_authority, vault_seeds) = vault_authority_and_signer(
+            game_state,
+            ctx.accounts.shared_escrow.as_ref(),
+            &ctx.accounts.config,
+        )?;
+        let vault_seeds: Vec<&[u8]> = vault_seeds.iter().map(Vec::as_slice).collect();
+        let vault_signer = &[&vault_seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let vault = ctx.accounts.vault.to_account_info();
+
+        // Hold referral rewards until each referrer withdraws them
+        let total_referral_fee = initiator_referral_fee + challenger_referral_fee;
+
+        if total_referral_fee > 0 {
+            transfer_from_vault(
+                &vault,
+                &ctx.accounts.referral_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                total_referral_fee,
+            )?;
+        }
+
+        // Pay the season's share of the fee into its prize pool while it is running
+        if let Some(season) = ctx
+            .accounts
+            .season
+            .as_mut()
+            .filter(|_| season_key.is_some() && protocol_fee > 0)
+        {
+            let season_vault = ctx
+                .accounts
+                .season_vault
+                .as_ref()
+                .ok_or(error!(CustomError::InvalidTokenAccount))?;
+
+            require!(
+                season_vault.key() == season.vault,
+                CustomError::InvalidTokenAccount
+            );
+
+            let season_fee = calculate_bps_share(protocol_fee, config.season_share_bps)?;
+            treasury_fee -= season_fee;
+
+            season.prize_pool = season
+                .prize_pool
+                .checked_add(season_fee)
+                .ok_or(error!(CustomError::Overflow))?;
+
+            transfer_from_vault(
+                &vault,
+                &season_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                season_fee,
+            )?;
+        }
+
+        if treasury_fee > 0 {
+            transfer_from_vault(
+                &vault,
+                &ctx.accounts.treasury_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                treasury_fee,
+            )?;
+        }
+
+        // Games funded from balances credit each holder's balance, leaving the stakes in the
+        // balance vault. SOL stakes are paid out as lamports to each holder's wallet, also
+        // when a session key settles for the caller. The house pool keeps its stake wrapped
+        // in its vault. Sides paid nothing are skipped.
+        let pay_lamports = is_native_mint(&game_state.stake_mint);
+        let mut sol_payouts = Vec::new();
+
+        for (holder, token_account, balance, balance_bump, payout) in [
+            (
+                &ctx.accounts.initiator_holder,
+                &ctx.accounts.initiator_token_account,
+                ctx.accounts.initiator_balance.as_deref_mut(),
+                ctx.bumps.initiator_balance,
+                initiator_payout,
+            ),
+            (
+                &ctx.accounts.challenger_holder,
+                &ctx.accounts.challenger_token_account,
+                ctx.accounts.challenger_balance.as_deref_mut(),
+                ctx.bumps.challenger_balance,
+                challenger_payout,
+            ),
+        ] {
+            if payout == 0 {
+                continue;
+            }
+
+            if game_state.balance_escrow {
+                credit_balance(
+                    balance,
+                    balance_bump,
+                    holder.key(),
+                    game_state.stake_mint,
+                    payout,
+                )?;
+            } else if pay_lamports && game_state.house_pool != Some(holder.key()) {
+                sol_payouts.push((holder.to_account_info(), payout));
+            } else {
+                transfer_from_vault(
+                    &vault,
+                    &token_account.to_account_info(),
+                    &ctx.accounts.stake_mint,
+                    &vault_authority,
+                    &cpi_program,
+                    vault_signer,
+                    payout,
+                )?;
+            }
+        }
+
+        unwrap_sol(
+            &vault,
+            &ctx.accounts.stake_mint,
+            &vault_authority,
+            vault_signer,
+            &ctx.accounts.sol_payout.to_account_info(),
+            ctx.bumps.sol_payout,
+            &ctx.accounts.payer.to_account_info(),
+            &sol_payouts,
+            &ctx.accounts.system_program.to_account_info(),
+            &cpi_program,
+        )?;
+
+        // The whole pot has left escrow
+        game_state.release_escrow(total_pot, ctx.accounts.shared_escrow.as_deref_mut())?;
+        ctx.accounts.vault.reload()?;
+        game_state.validate_escrow(
+            ctx.accounts.vault.amount,
+            ctx.accounts.shared_escrow.as_deref(),
+        )?;
+
+        // Close the emptied game vault, refunding its rent to whoever paid for it
+        if game_state.has_own_vault() {
+            close_empty_vault(
+                &mut ctx.accounts.vault,
+                &ctx.accounts.rent_payer.to_account_info(),
+                &game_state.to_account_info(),
+                &ctx.accounts.token_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        // Burn each redeemed position token as its permanent delegate from whichever account
+        // holds it, if passed, and close the mints. A mint can only be closed once its token
+        // is gone.
+        let join_rent_payer = ctx.accounts.join_rent_payer.to_account_info();
+        let position_token_program = ctx.accounts.position_token_program.to_account_info();
+
+        let positions = [
+            (
+                &ctx.accounts.initiator_position_mint,
+                ctx.accounts.initiator_position_account.as_ref(),
+            ),
+            (
+                &ctx.accounts.challenger_position_mint,
+                ctx.accounts.challenger_position_account.as_ref(),
+            ),
+        ];
+        for (position_mint, position_account) in positions {
+            if let Some(position_account) = position_account {
+                burn_position_token(
+                    &position_mint.to_account_info(),
+                    &position_account.to_account_info(),
+                    &game_state.to_account_info(),
+                    &join_rent_payer,
+                    &position_token_program,
+                    signer,
+                )?;
+            } else if position_mint.supply == 0 {
+                close_token_account(
+                    &position_mint.to_account_info(),
+                    &join_rent_payer,
+                    &game_state.to_account_info(),
+                    &position_token_program,
+                    signer,
+                )?;
+            }
+        }
+
+        // Close the emptied position accounts of the caller, when they signed for
+        // themselves, and of the house pool
+        let position_accounts = [
+            ctx.accounts.initiator_position_account.as_ref(),
+            ctx.accounts.challenger_position_account.as_ref(),
+        ];
+        for position_account in position_accounts.into_iter().flatten() {
+            let owner = position_account.owner;
+            let position_account = position_account.to_account_info();
+
+            if house_pool_settling && owner == player_key {
+                let house_pool = ctx
+                    .accounts
+                    .house_pool
+                    .as_ref()
+                    .ok_or(error!(CustomError::InvalidHousePool))?;
+                let house_pool_seeds = &[
+                    b"house_pool".as_ref(),
+                    house_pool.stake_mint.as_ref(),
+                    &[house_pool.bump],
+                ];
+                close_token_account(
+                    &position_account,
+                    &join_rent_payer,
+                    &house_pool.to_account_info(),
+                    &position_token_program,
+                    &[&house_pool_seeds[..]],
+                )?;
+            } else if owner == player_key && ctx.accounts.authority.key() == player_key {
+                let player = ctx.accounts.player.to_account_info();
+                close_token_account(
+                    &position_account,
+                    &player,
+                    &player,
+                    &position_token_program,
+                    &[],
+                )?;
+            }
+        }
+
+        emit!(PositionRedeemed {
+            game_id,
+            prediction: game_state.initiator_prediction,
+            holder: game_state.initiator_holder,
+            amount: initiator_payout,
+            timestamp: current_time,
+        });
+
+        emit!(PositionRedeemed {
+            game_id,
+            prediction: game_state.get_challenger_prediction(),
+            holder: ctx.accounts.challenger_holder.key(),
+            amount: challenger_payout,
+            timestamp: current_time,
+        });
+
+        // Both sides are settled, so close the game and refund its rent. Tournament games
+        // stay open until their result is reported.
+        if !game_state.is_tournament_game() {
+            game_state.close(ctx.accounts.rent_payer.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Closes an active game whose price feed has stopped updating, so both sides can be
+    /// refunded without waiting for the timeout.
+    ///
+    /// Anyone can call this once the game's feed has gone `ORACLE_OUTAGE_GRACE_SECONDS`
+    /// without a new round and none of the game's price sources, if it has any, is fresh
+    /// enough to fall back on. A fresh Pyth update can always be posted, so Pyth sources
+    /// must be passed fresh and only stale Chainlink sources count as unavailable. Each side
+    /// then redeems its full entry amount through `draw_game`, whatever the game's timeout
+    /// policy. An outage is no contest, so neither player's rating, record or season points
+    /// change.
+    ///
+    /// This function:
+    /// - Validates the game is active
+    /// - Verifies the feed's latest round is older than the grace period
+    /// - Marks the game as a draw, with the outage in its `GameClosed` details
+    pub fn declare_oracle_outage(
+        ctx: Context<DeclareOracleOutage>,
+        game_id: u64,
+        initiator: Pubkey,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Games still waiting on their start price are covered too, as they can't start
+        require!(
+            game_state.is_correct_game_id(game_id),
+            CustomError::IncorrectGameId
+        );
+        require!(
+            game_state.is_creator(initiator),
+            CustomError::IncorrectInitiator
+        );
+        require!(game_state.is_active(), CustomError::GameNotActive);
+
+        let (last_price, last_round_timestamp) = get_chainlink_price(
+            &ctx.accounts.chainlink_program,
+            &ctx.accounts.chainlink_feed,
+            current_time,
+        )?;
+
+        require!(
+            current_time - last_round_timestamp > ORACLE_OUTAGE_GRACE_SECONDS
+                && are_price_sources_unavailable(
+                    ctx.accounts.chainlink_program.key,
+                    game_state.price_sources.as_ref(),
+                    ctx.remaining_accounts,
+                    current_time,
+                )?,
+            CustomError::OracleNotStale
+        );
+
+        // Mark game as closed. Without a final price both sides are refunded.
+        game_state.closed_at = Some(current_time);
+        game_state.status = GameStatus::Draw;
+
+        // An outage is no contest, so neither player's rating changes
+        emit!(GameClosed {
+            game_id,
+            status: GameStatus::Draw,
+            details: GameStatusDetails::OracleOutage {
+                price_feed: game_state.price_feed,
+                last_price,
+                last_round_timestamp,
+            },
+            initiator_rating_delta: 0,
+            challenger_rating_delta: 0,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Cancels a game and allows the initiator to withdraw their stake.
+    ///
+    /// Only allowed if no challenger has joined yet.
+    ///
+    /// This function:
+    /// - Ensures the game is still joinable and hasn't closed
+    /// - Returns the entry amount to the initiator, or to their internal balance if the game
+    ///   was funded from it
+    /// - Marks the game as cancelled
+    /// - Records the cancellation in the initiator's player profile
+    /// - Closes the game, unless it is a tournament match
+    pub fn cancel_game(ctx: Context<CancelGame>, game_id: u64) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let initiator = &ctx.accounts.initiator;
+        let initiator_key = initiator.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        game_state.validate_withdraw(game_id, initiator_key)?;
+
+        game_state.closed_at = Some(current_time);
+        game_state.status = GameStatus::Cancelled;
+
+        let initiator_profile = &mut ctx.accounts.initiator_profile;
+        initiator_profile.initialize(
+            initiator_key,
+            game_state.stake_mint,
+            ctx.bumps.initiator_profile,
+        );
+        initiator_profile.record_cancellation()?;
+
+        // Return the entry amount to initiator
+        let seeds = &[
+            b"game_state",
+            initiator_key.as_ref(),
+            &game_id.to_le_bytes(),
+            &[game_state.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let (vault_authority, vault_seeds) = vault_authority_and_signer(
+            game_state,
+            ctx.accounts.shared_escrow.as_ref(),
+            &ctx.accounts.config,
+        )?;
+        let vault_seeds: Vec<&[u8]> = vault_seeds.iter().map(Vec::as_slice).collect();
+        let vault_signer = &[&vault_seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // Stakes from the initiator's balance go back to it, staying in the balance vault.
+        // Pay SOL stakes out as lamports.
+        if game_state.balance_escrow {
+            let initiator_balance = ctx
+                .accounts
+                .initiator_balance
+                .as_mut()
+                .ok_or(error!(CustomError::PlayerBalanceRequired))?;
+
+            initiator_balance.credit(game_state.entry_amount)?;
+        } else if is_native_mint(&game_state.stake_mint) {
+            unwrap_sol(
+                &ctx.accounts.vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                vault_signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(
+                    ctx.accounts.initiator.to_account_info(),
+                    game_state.entry_amount,
+                )],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.vault.to_account_info(),
+                &ctx.accounts.initiator_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &vault_authority,
+                &cpi_program,
+                vault_signer,
+                game_state.entry_amount,
+            )?;
+        }
+
+        let entry_amount = game_state.entry_amount;
+        game_state.release_escrow(entry_amount, ctx.accounts.shared_escrow.as_deref_mut())?;
+        ctx.accounts.vault.reload()?;
+        game_state.validate_escrow(
+            ctx.accounts.vault.amount,
+            ctx.accounts.shared_escrow.as_deref(),
+        )?;
+
+        // Close the emptied game vault, refunding its rent to whoever paid for it
+        if game_state.has_own_vault() {
+            close_empty_vault(
+                &mut ctx.accounts.vault,
+                &ctx.accounts.rent_payer.to_account_info(),
+                &game_state.to_account_info(),
+                &ctx.accounts.token_program.to_account_info(),
+                signer,
+            )?;
+        }
+
+        emit!(GameClosed {
+            game_id,
+            status: GameStatus::Cancelled,
             details: GameStatusDetails::None,
+            initiator_rating_delta: 0,
+            challenger_rating_delta: 0,
+            timestamp: current_time,
+        });
+
+        // Close the cancelled game, refunding its rent. Tournament games stay open until
+        // their result is reported.
+        if !game_state.is_tournament_game() {
+            game_state.close(ctx.accounts.rent_payer.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    /// Opens a season for games staked in the given mint.
+    ///
+    /// The season becomes the mint's current season, which `close_game` requires from then
+    /// on. Games won between `start_at` and `end_at` earn season points and fund its prize
+    /// pool. `payout_curve` holds the share of the pool paid to each rank in basis points,
+    /// best rank first. Seasons for a mint can't overlap, so the previous one is passed as
+    /// `current_season` and must end by `start_at`.
+    ///
+    /// This function:
+    /// - Ensures the caller is the admin
+    /// - Validates the schedule and payout curve
+    /// - Ensures the mint's previous season has ended by the time this one starts
+    /// - Creates the season and its prize vault
+    pub fn create_season(
+        ctx: Context<CreateSeason>,
+        season_id: u64,
+        start_at: i64,
+        end_at: i64,
+        payout_curve: Vec<u16>,
+    ) -> Result<()> {
+        let curve_total: u32 = payout_curve.iter().map(|&bps| bps as u32).sum();
+
+        require!(
+            end_at > start_at
+                && !payout_curve.is_empty()
+                && payout_curve.len() <= MAX_SEASON_WINNERS
+                && curve_total <= BPS_DENOMINATOR as u32,
+            CustomError::InvalidSeason
+        );
+
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+
+        if let Some(current_season_key) = allowed_mint.season {
+            let current_season = ctx
+                .accounts
+                .current_season
+                .as_ref()
+                .ok_or(error!(CustomError::SeasonRequired))?;
+
+            require!(
+                current_season.key() == current_season_key && current_season.end_at <= start_at,
+                CustomError::InvalidSeason
+            );
+        }
+
+        let season = &mut ctx.accounts.season;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        allowed_mint.season = Some(season.key());
+
+        season.season_id = season_id;
+        season.stake_mint = ctx.accounts.stake_mint.key();
+        season.vault = ctx.accounts.season_vault.key();
+        season.start_at = start_at;
+        season.end_at = end_at;
+        season.payout_curve = payout_curve.clone();
+        season.bump = ctx.bumps.season;
+
+        emit!(SeasonCreated {
+            season_id,
+            stake_mint: season.stake_mint,
+            start_at,
+            end_at,
+            payout_curve,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Pays a player their share of a finished season's prize pool.
+    ///
+    /// Ranks come from the season's on-chain leaderboard, so any top-N player can claim
+    /// without an off-chain snapshot.
+    ///
+    /// This function:
+    /// - Verifies the season has ended
+    /// - Looks up the player's rank and ensures it hasn't been claimed
+    /// - Transfers the rank's share of the prize pool to the player
+    pub fn claim_season_prize(ctx: Context<ClaimSeasonPrize>, season_id: u64) -> Result<()> {
+        let season = &mut ctx.accounts.season;
+        let player_key = ctx.accounts.player.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(season.is_ended(current_time), CustomError::SeasonNotEnded);
+
+        let rank = season
+            .get_rank(player_key)
+            .ok_or(error!(CustomError::NotASeasonWinner))?;
+        let points = season.leaderboard[rank].points;
+        let amount = season.claim_prize(rank)?;
+
+        let stake_mint_key = season.stake_mint;
+        let seeds = &[
+            b"season",
+            stake_mint_key.as_ref(),
+            &season_id.to_le_bytes(),
+            &[season.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // Pay SOL prizes out as lamports
+        if is_native_mint(&stake_mint_key) {
+            unwrap_sol(
+                &ctx.accounts.season_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &season.to_account_info(),
+                signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.player.to_account_info(), amount)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.season_vault.to_account_info(),
+                &ctx.accounts.player_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &season.to_account_info(),
+                &cpi_program,
+                signer,
+                amount,
+            )?;
+        }
+
+        emit!(SeasonPrizeClaimed {
+            season_id,
+            stake_mint: stake_mint_key,
+            player: player_key,
+            rank: rank as u8,
+            points,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Registers the player's referrer.
+    ///
+    /// A player can only register a referrer once. From then on, every game of theirs settled
+    /// through `close_game` credits the referrer with a share of the protocol fee.
+    ///
+    /// This function:
+    /// - Ensures the player isn't referring themselves
+    /// - Records the referrer in the player's referral account
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+        let player_key = ctx.accounts.player.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(referrer != player_key, CustomError::InvalidReferrer);
+
+        let referral = &mut ctx.accounts.referral;
+
+        referral.player = player_key;
+        referral.referrer = referrer;
+        referral.registered_at = current_time;
+        referral.bump = ctx.bumps.referral;
+
+        emit!(ReferrerRegistered {
+            player: player_key,
+            referrer,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraws the referral rewards a referrer has earned from one referred player in
+    /// one stake mint.
+    ///
+    /// This function:
+    /// - Verifies the caller is the player's registered referrer
+    /// - Transfers the accumulated rewards from the referral vault to the referrer
+    /// - Resets the player's outstanding referral rewards
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        let player_profile = &mut ctx.accounts.player_profile;
+        let current_time = Clock::get()?.unix_timestamp;
+        let amount = player_profile.referral_rewards;
+
+        require!(amount > 0, CustomError::NoReferralRewards);
+
+        player_profile.referral_rewards = 0;
+
+        let seeds = &[b"config".as_ref(), &[ctx.accounts.config.bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // Pay SOL rewards out as lamports
+        if is_native_mint(&ctx.accounts.stake_mint.key()) {
+            unwrap_sol(
+                &ctx.accounts.referral_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &ctx.accounts.config.to_account_info(),
+                signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.referrer.to_account_info(), amount)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.referral_vault.to_account_info(),
+                &ctx.accounts.referrer_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &ctx.accounts.config.to_account_info(),
+                &cpi_program,
+                signer,
+                amount,
+            )?;
+        }
+
+        emit!(ReferralRewardsClaimed {
+            referrer: ctx.accounts.referrer.key(),
+            player: player_profile.player,
+            stake_mint: player_profile.stake_mint,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Creates a single-elimination tournament.
+    ///
+    /// `payout_curve` holds the share of the pot paid to each finishing rank in basis
+    /// points: the champion first, then the runner-up, then the players knocked out in
+    /// each earlier round. Players knocked out in the same round share their ranks evenly.
+    ///
+    /// This function:
+    /// - Ensures the caller is the admin
+    /// - Validates the entry fee, registration deadline, bracket size and payout curve
+    /// - Creates the tournament and its pot vault
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        entry_fee: u64,
+        max_players: u8,
+        registration_ends_at: i64,
+        payout_curve: Vec<u16>,
+    ) -> Result<()> {
+        let curve_total: u32 = payout_curve.iter().map(|&bps| bps as u32).sum();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            entry_fee > 0
+                && registration_ends_at > current_time
+                && max_players >= 2
+                && max_players as usize <= MAX_TOURNAMENT_PLAYERS
+                && !payout_curve.is_empty()
+                && payout_curve.len() <= max_players as usize
+                && curve_total == BPS_DENOMINATOR as u32,
+            CustomError::InvalidTournament
+        );
+
+        let tournament = &mut ctx.accounts.tournament;
+
+        tournament.tournament_id = tournament_id;
+        tournament.stake_mint = ctx.accounts.stake_mint.key();
+        tournament.vault = ctx.accounts.tournament_vault.key();
+        tournament.entry_fee = entry_fee;
+        tournament.max_players = max_players;
+        tournament.registration_ends_at = registration_ends_at;
+        tournament.status = TournamentStatus::Registration;
+        tournament.payout_curve = payout_curve.clone();
+        tournament.bump = ctx.bumps.tournament;
+
+        emit!(TournamentCreated {
+            tournament_id,
+            stake_mint: tournament.stake_mint,
+            entry_fee,
+            max_players,
+            registration_ends_at,
+            payout_curve,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Registers a player for a tournament by paying the entry fee into the pot.
+    ///
+    /// This function:
+    /// - Ensures registration is open and the tournament isn't full
+    /// - Transfers the entry fee into the tournament vault
+    /// - Seeds the player after everyone already registered
+    pub fn register_for_tournament(
+        ctx: Context<RegisterForTournament>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let player_key = ctx.accounts.player.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        tournament.register(player_key, current_time)?;
+        tournament.pot = tournament
+            .pot
+            .checked_add(tournament.entry_fee)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        deposit_stake(
+            &ctx.accounts.player.to_account_info(),
+            &ctx.accounts.player_token_account.to_account_info(),
+            &ctx.accounts.tournament_vault.to_account_info(),
+            &ctx.accounts.stake_mint,
+            None,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            tournament.entry_fee,
+        )?;
+
+        emit!(TournamentPlayerRegistered {
+            tournament_id,
+            player: player_key,
+            seed: (tournament.players.len() - 1) as u8,
+            pot: tournament.pot,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Calls off a tournament before it starts so every player can reclaim their entry fee.
+    ///
+    /// The admin can cancel a tournament still open for registration. Anyone can cancel
+    /// one whose registration closed with fewer than two players, which could never start.
+    ///
+    /// This function:
+    /// - Validates the tournament hasn't started and the caller may cancel it
+    /// - Marks the tournament as cancelled
+    pub fn cancel_tournament(ctx: Context<CancelTournament>, tournament_id: u64) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let by_admin = ctx.accounts.authority.key() == ctx.accounts.config.admin;
+        tournament.cancel(by_admin, current_time)?;
+
+        emit!(TournamentCancelled {
+            tournament_id,
+            players: tournament.players.len() as u8,
+            pot: tournament.pot,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Refunds a player's entry fee from a cancelled tournament.
+    ///
+    /// This function:
+    /// - Verifies the tournament is cancelled and the player is still registered
+    /// - Removes the player from the tournament
+    /// - Transfers the entry fee back to the player
+    pub fn claim_tournament_refund(
+        ctx: Context<ClaimTournamentRefund>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let player_key = ctx.accounts.player.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let amount = tournament.refund(player_key)?;
+
+        let seeds = &[
+            b"tournament".as_ref(),
+            &tournament_id.to_le_bytes(),
+            &[tournament.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // Pay SOL refunds out as lamports
+        if is_native_mint(&tournament.stake_mint) {
+            unwrap_sol(
+                &ctx.accounts.tournament_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &tournament.to_account_info(),
+                signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.player.to_account_info(), amount)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.tournament_vault.to_account_info(),
+                &ctx.accounts.player_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &tournament.to_account_info(),
+                &cpi_program,
+                signer,
+                amount,
+            )?;
+        }
+
+        emit!(TournamentRefundClaimed {
+            tournament_id,
+            player: player_key,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Draws the first round of the bracket. Anyone can start a tournament once
+    /// registration has closed or every spot is taken.
+    ///
+    /// This function:
+    /// - Ensures at least two players registered
+    /// - Pairs the best seeds with the worst, giving byes to the top seeds
+    pub fn start_tournament(ctx: Context<StartTournament>, tournament_id: u64) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        tournament.start(current_time)?;
+
+        emit!(TournamentStarted {
+            tournament_id,
+            players: tournament.players.len() as u8,
+            pot: tournament.pot,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Creates the game for a tournament match.
+    ///
+    /// The match's home player picks the prediction and only the away player can join.
+    /// Tournament games carry no stake; the pot is paid out when the tournament ends.
+    ///
+    /// This function:
+    /// - Validates the caller is the home player of an undecided match
+    /// - Links the game to the match
+    /// - Initializes a zero-stake game reserved for the away player
+    pub fn create_tournament_game(
+        ctx: Context<CreateTournamentGame>,
+        _tournament_id: u64,
+        match_index: u8,
+        game_id: u64,
+        prediction: PricePrediction,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let initiator_key = ctx.accounts.initiator.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let away = ctx.accounts.tournament.assign_match_game(
+            match_index as usize,
+            initiator_key,
+            game_state.key(),
+        )?;
+
+        // Take the initial price from the market's current price sources, which the game
+        // keeps for the rest of its life
+        let price_sources = load_price_sources(&ctx.accounts.price_sources)?;
+        let initial_price = get_price(
+            &ctx.accounts.chainlink_program,
+            &ctx.accounts.chainlink_feed,
+            price_sources.as_ref(),
+            ctx.remaining_accounts,
+            current_time,
+        )?;
+
+        game_state.game_id = game_id;
+        game_state.initiator = initiator_key;
+        game_state.initiator_holder = game_state.initiator;
+        game_state.initiator_prediction = prediction;
+        game_state.stake_mint = ctx.accounts.stake_mint.key();
+        game_state.initial_price = initial_price;
+        game_state.price_feed = ctx.accounts.chainlink_feed.key();
+        game_state.price_sources = price_sources;
+        game_state.created_at = current_time;
+        game_state.increase_threshold_bps = WIN_PRICE_THRESHOLD_BPS;
+        game_state.decrease_threshold_bps = WIN_PRICE_THRESHOLD_BPS;
+        game_state.status = GameStatus::Pending;
+        game_state.reserved_challenger = Some(away);
+        game_state.vault = ctx.accounts.vault.key();
+        game_state.rent_payer = ctx.accounts.payer.key();
+        game_state.bump = ctx.bumps.game_state;
+
+        emit!(GameCreated {
+            game_id,
+            status: GameStatus::Pending,
+            initiator: initiator_key,
+            prediction,
+            initial_price,
+            stake_mint: game_state.stake_mint,
+            entry_amount: 0,
+            start_at: None,
+            anchor_price_at_join: false,
+            timeout_policy: TimeoutPolicy::Refund,
+            increase_threshold_bps: WIN_PRICE_THRESHOLD_BPS,
+            decrease_threshold_bps: WIN_PRICE_THRESHOLD_BPS,
+            win_condition: WinCondition::Instant,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Advances the winner of a tournament match. Anyone can report a decided match.
+    ///
+    /// - A completed game is won by the player who predicted the price move
+    /// - A drawn game goes to the higher seed
+    /// - A cancelled game, or no game within the round timeout, goes to the away player
+    /// - A game the away player hasn't joined by the game timeout goes to the home player
+    ///
+    /// This function:
+    /// - Determines the match result from the linked game
+    /// - Advances the winner and moves to the next round once every match is decided
+    /// - Crowns the champion after the final
+    /// - Closes the match's game, cancelling it if the away player never joined
+    pub fn report_tournament_match(
+        ctx: Context<ReportTournamentMatch>,
+        tournament_id: u64,
+        match_index: u8,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let current_time = Clock::get()?.unix_timestamp;
+        let match_index = match_index as usize;
+
+        let (home, away) = tournament.get_match(match_index)?;
+        let match_game = tournament.match_games[match_index];
+
+        let home_won = match ctx.accounts.game_state.as_ref() {
+            Some(game_state) => {
+                require!(
+                    game_state.key() == match_game,
+                    CustomError::InvalidTournamentGame
+                );
+
+                match game_state.status {
+                    GameStatus::Complete(winning_prediction) => {
+                        winning_prediction == game_state.initiator_prediction
+                    }
+                    GameStatus::Draw => tournament.get_seed(home) < tournament.get_seed(away),
+                    GameStatus::Cancelled => false,
+                    GameStatus::Pending => {
+                        require!(
+                            current_time - game_state.created_at >= GAME_TIMEOUT_SECONDS,
+                            CustomError::MatchNotDecided
+                        );
+                        true
+                    }
+                    GameStatus::Active => return err!(CustomError::MatchNotDecided),
+                }
+            }
+            None => {
+                require!(
+                    match_game == Pubkey::default(),
+                    CustomError::InvalidTournamentGame
+                );
+                require!(
+                    current_time - tournament.round_started_at >= TOURNAMENT_ROUND_TIMEOUT_SECONDS,
+                    CustomError::MatchNotDecided
+                );
+                false
+            }
+        };
+
+        let (winner, loser) = if home_won { (home, away) } else { (away, home) };
+        let round = tournament.round;
+        let completed = tournament.record_match_result(match_index, winner, loser, current_time);
+
+        emit!(TournamentMatchDecided {
+            tournament_id,
+            round,
+            match_index: match_index as u8,
+            game: ctx.accounts.game_state.as_ref().map(|game| game.key()),
+            winner,
+            loser,
+            timestamp: current_time,
+        });
+
+        if completed {
+            emit!(TournamentCompleted {
+                tournament_id,
+                champion: winner,
+                pot: tournament.pot,
+                timestamp: current_time,
+            });
+        }
+
+        // Close the decided match's game, refunding its rent. A game the away player never
+        // joined is cancelled first, closing its empty vault.
+        if let Some(game_state) = ctx.accounts.game_state.as_mut() {
+            let rent_payer = ctx
+                .accounts
+                .rent_payer
+                .as_ref()
+                .filter(|rent_payer| rent_payer.key() == game_state.rent_payer)
+                .ok_or(error!(CustomError::IncorrectRentPayer))?
+                .to_account_info();
+
+            if game_state.status == GameStatus::Pending {
+                let vault = ctx
+                    .accounts
+                    .vault
+                    .as_mut()
+                    .filter(|vault| vault.key() == game_state.vault)
+                    .ok_or(error!(CustomError::InvalidTokenAccount))?;
+                let token_program = ctx
+                    .accounts
+                    .token_program
+                    .as_ref()
+                    .ok_or(error!(CustomError::InvalidTokenAccount))?;
+
+                let seeds = &[
+                    b"game_state",
+                    game_state.initiator.as_ref(),
+                    &game_state.game_id.to_le_bytes(),
+                    &[game_state.bump],
+                ];
+                close_empty_vault(
+                    vault,
+                    &rent_payer,
+                    &game_state.to_account_info(),
+                    &token_program.to_account_info(),
+                    &[&seeds[..]],
+                )?;
+            }
+
+            game_state.close(rent_payer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Pays a player their share of a finished tournament's pot.
+    ///
+    /// The champion's prize also includes the shares of ranks nobody reached, in brackets
+    /// smaller than the payout curve, and any rounding dust, so the whole pot is paid out.
+    ///
+    /// This function:
+    /// - Verifies the tournament is complete
+    /// - Looks up the player's finishing ranks and ensures the prize hasn't been claimed
+    /// - Transfers the ranks' share of the pot to the player
+    pub fn claim_tournament_prize(
+        ctx: Context<ClaimTournamentPrize>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let tournament = &mut ctx.accounts.tournament;
+        let player_key = ctx.accounts.player.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            tournament.status == TournamentStatus::Complete,
+            CustomError::TournamentNotComplete
+        );
+
+        let (rank, amount) = tournament.claim_prize(player_key)?;
+
+        let seeds = &[
+            b"tournament".as_ref(),
+            &tournament_id.to_le_bytes(),
+            &[tournament.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // Pay SOL prizes out as lamports
+        if is_native_mint(&tournament.stake_mint) {
+            unwrap_sol(
+                &ctx.accounts.tournament_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &tournament.to_account_info(),
+                signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.player.to_account_info(), amount)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.tournament_vault.to_account_info(),
+                &ctx.accounts.player_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &tournament.to_account_info(),
+                &cpi_program,
+                signer,
+                amount,
+            )?;
+        }
+
+        emit!(TournamentPrizeClaimed {
+            tournament_id,
+            player: player_key,
+            rank: rank as u8,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Deposits tokens into the owner's internal balance for a stake mint.
+    ///
+    /// Balances can fund the stakes of `create_game` and `join_game` without a token
+    /// transfer from the player's wallet. Transfer fees are covered by the owner so the
+    /// full `amount` is credited.
+    ///
+    /// This function:
+    /// - Transfers `amount` into the mint's balance vault, wrapping SOL if needed
+    /// - Credits the owner's balance
+    pub fn deposit_balance(ctx: Context<DepositBalance>, amount: u64) -> Result<()> {
+        require!(amount > 0, CustomError::InvalidEntryAmount);
+
+        let owner_key = ctx.accounts.owner.key();
+        let stake_mint_key = ctx.accounts.stake_mint.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        deposit_stake(
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.owner_token_account.to_account_info(),
+            &ctx.accounts.balance_vault.to_account_info(),
+            &ctx.accounts.stake_mint,
+            None,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+
+        let owner_balance = &mut ctx.accounts.owner_balance;
+        owner_balance.initialize(owner_key, stake_mint_key, ctx.bumps.owner_balance);
+        owner_balance.credit(amount)?;
+
+        emit!(BalanceDeposited {
+            owner: owner_key,
+            stake_mint: stake_mint_key,
+            amount,
+            balance: owner_balance.amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraws tokens from the owner's internal balance.
+    ///
+    /// This function:
+    /// - Debits the owner's balance
+    /// - Transfers `amount` out of the mint's balance vault, unwrapping SOL if needed
+    pub fn withdraw_balance(ctx: Context<WithdrawBalance>, amount: u64) -> Result<()> {
+        let owner_balance = &mut ctx.accounts.owner_balance;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        owner_balance.debit(amount)?;
+
+        let seeds = &[b"config".as_ref(), &[ctx.accounts.config.bump]];
+        let signer = &[&seeds[..]];
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // Pay SOL out as lamports
+        if is_native_mint(&owner_balance.stake_mint) {
+            unwrap_sol(
+                &ctx.accounts.balance_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &ctx.accounts.config.to_account_info(),
+                signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.owner.to_account_info(), amount)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.balance_vault.to_account_info(),
+                &ctx.accounts.owner_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &ctx.accounts.config.to_account_info(),
+                &cpi_program,
+                signer,
+                amount,
+            )?;
+        }
+
+        emit!(BalanceWithdrawn {
+            owner: owner_balance.owner,
+            stake_mint: owner_balance.stake_mint,
+            amount,
+            balance: owner_balance.amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Creates or updates the house pool for a stake mint.
+    ///
+    /// The pool is a counterparty funded by liquidity providers that joins pending games
+    /// through `house_join_game`.
+    ///
+    /// This function:
+    /// - Ensures the caller is the admin
+    /// - Sets the pool's per-market and per-direction exposure limits
+    /// - Creates the pool's vault and share mint on first use
+    pub fn set_house_pool(
+        ctx: Context<SetHousePool>,
+        max_market_exposure: u64,
+        max_direction_exposure: u64,
+    ) -> Result<()> {
+        let house_pool = &mut ctx.accounts.house_pool;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        house_pool.stake_mint = ctx.accounts.stake_mint.key();
+        house_pool.share_mint = ctx.accounts.share_mint.key();
+        house_pool.max_market_exposure = max_market_exposure;
+        house_pool.max_direction_exposure = max_direction_exposure;
+        house_pool.bump = ctx.bumps.house_pool;
+
+        emit!(HousePoolUpdated {
+            stake_mint: house_pool.stake_mint,
+            max_market_exposure,
+            max_direction_exposure,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Configures the price sources for a market, keyed by its Chainlink feed.
+    ///
+    /// Games created once the sources are configured keep a copy of them, and are priced
+    /// from those sources instead of the Chainlink feed alone for the rest of their life, so
+    /// changing or removing the sources only affects new games. The sources' accounts are
+    /// passed as remaining accounts in the configured order, and `policy` combines their
+    /// fresh prices: the first one with `Fallback`, or the median with `Median`. Sustained
+    /// win conditions and timeout splits are still proven with the Chainlink feed's rounds.
+    pub fn set_price_sources(
+        ctx: Context<SetPriceSources>,
+        policy: PricePolicy,
+        sources: Vec<PriceSource>,
+    ) -> Result<()> {
+        let price_sources = &mut ctx.accounts.price_sources;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        price_sources.price_feed = ctx.accounts.chainlink_feed.key();
+        price_sources.policy = policy;
+        price_sources.sources = sources;
+        price_sources.bump = ctx.bumps.price_sources;
+        price_sources.validate()?;
+
+        emit!(PriceSourcesUpdated {
+            price_feed: price_sources.price_feed,
+            policy,
+            source_count: price_sources.sources.len() as u8,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Removes a market's price sources, refunding their rent to the admin.
+    ///
+    /// New games on the market are priced with its Chainlink feed alone again. Existing
+    /// games keep the sources they were created with.
+    pub fn close_price_sources(ctx: Context<ClosePriceSources>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+
+        emit!(PriceSourcesClosed {
+            price_feed: ctx.accounts.price_sources.price_feed,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Deposits liquidity into the house pool in exchange for share tokens.
+    ///
+    /// Shares are priced at the pool's net asset value: its free liquidity plus the full pot
+    /// of each active game it plays, the most those games can return. The pool's winnings
+    /// and losses from settled games move the share price. The pool's first deposit locks
+    /// `HOUSE_POOL_LOCKED_SHARES` of its shares so nobody can redeem them.
+    ///
+    /// This function:
+    /// - Prices `amount` in shares at the current share price
+    /// - Transfers `amount` into the pool's vault, wrapping SOL if needed
+    /// - Mints the shares to the provider
+    pub fn deposit_liquidity(ctx: Context<DepositLiquidity>, amount: u64) -> Result<()> {
+        require!(amount > 0, CustomError::InvalidLiquidityAmount);
+
+        let house_pool = &mut ctx.accounts.house_pool;
+        let provider_key = ctx.accounts.provider.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let shares = house_pool.issue_shares_for_deposit(
+            amount,
+            ctx.accounts.house_pool_vault.amount,
+            ctx.accounts.share_mint.supply,
+        )?;
+
+        require!(shares > 0, CustomError::InvalidLiquidityAmount);
+
+        deposit_stake(
+            &ctx.accounts.provider.to_account_info(),
+            &ctx.accounts.provider_token_account.to_account_info(),
+            &ctx.accounts.house_pool_vault.to_account_info(),
+            &ctx.accounts.stake_mint,
+            None,
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+
+        let seeds = &[
+            b"house_pool".as_ref(),
+            house_pool.stake_mint.as_ref(),
+            &[house_pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            to: ctx.accounts.provider_share_account.to_account_info(),
+            authority: house_pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+
+        token_interface::mint_to(cpi_ctx, shares)?;
+
+        emit!(LiquidityDeposited {
+            provider: provider_key,
+            stake_mint: house_pool.stake_mint,
+            amount,
+            shares,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Redeems share tokens for their value in the house pool.
+    ///
+    /// Shares are redeemed against the pool's free liquidity alone. Stakes locked in active
+    /// games count for nothing until they settle, so a provider leaving mid-game leaves
+    /// their part of those games to the providers who stay.
+    ///
+    /// This function:
+    /// - Prices the shares at the current share price
+    /// - Burns the shares and transfers the amount out, unwrapping SOL if needed
+    pub fn withdraw_liquidity(ctx: Context<WithdrawLiquidity>, shares: u64) -> Result<()> {
+        let house_pool = &ctx.accounts.house_pool;
+        let provider_key = ctx.accounts.provider.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let amount = house_pool.get_withdrawal_amount(
+            shares,
+            ctx.accounts.house_pool_vault.amount,
+            ctx.accounts.share_mint.supply,
+        )?;
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.share_mint.to_account_info(),
+            from: ctx.accounts.provider_share_account.to_account_info(),
+            authority: ctx.accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program.clone(), cpi_accounts);
+
+        token_interface::burn(cpi_ctx, shares)?;
+
+        let seeds = &[
+            b"house_pool".as_ref(),
+            house_pool.stake_mint.as_ref(),
+            &[house_pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Pay SOL out as lamports
+        if is_native_mint(&house_pool.stake_mint) {
+            unwrap_sol(
+                &ctx.accounts.house_pool_vault.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &house_pool.to_account_info(),
+                signer,
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.payer.to_account_info(),
+                &[(ctx.accounts.provider.to_account_info(), amount)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        } else {
+            transfer_from_vault(
+                &ctx.accounts.house_pool_vault.to_account_info(),
+                &ctx.accounts.provider_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &house_pool.to_account_info(),
+                &cpi_program,
+                signer,
+                amount,
+            )?;
+        }
+
+        emit!(LiquidityWithdrawn {
+            provider: provider_key,
+            stake_mint: house_pool.stake_mint,
+            amount,
+            shares,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Joins a pending game with the house pool as the challenger.
+    ///
+    /// Anyone can call this, e.g. a keeper cranking unjoined games. The pool takes the side
+    /// opposite the initiator, staking from its free liquidity. Anyone can then redeem the
+    /// pool's winning or drawn position through `close_game` or `draw_game`, which pays
+    /// back into the pool's vault. Only games created with `anchor_price_at_join` can be
+    /// joined, so the caller can't wait for the price to move against the initiator first.
+    /// `expected_refresh_nonce` must match the game's refresh nonce.
+    ///
+    /// This function:
+    /// - Validates the game is joinable at the current price of its own feed, and hasn't
+    ///   been refreshed since the caller read it
+    /// - Ensures the game's initial price is taken at join, and that it wasn't funded from
+    ///   an internal balance
+    /// - Locks the stake within the pool's limits for the game's market and direction
+    /// - Marks the game as started
+    /// - Records both sides' current ratings for settlement
+    /// - Moves the stake from the pool's vault into the game's vault
+    /// - Mints a single position token for each side, the challenger's to the pool
+    pub fn house_join_game(
+        ctx: Context<HouseJoinGame>,
+        game_id: u64,
+        initiator: Pubkey,
+        expected_refresh_nonce: u32,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let house_pool = &mut ctx.accounts.house_pool;
+        let house_pool_key = house_pool.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        game_state.validate_join(game_id, house_pool_key, initiator, current_time)?;
+        game_state.validate_refresh_nonce(expected_refresh_nonce)?;
+
+        // The pool takes whichever side is left, so it only joins games that start from the
+        // price at join, where nobody can pick a side the price has already moved towards
+        require!(
+            game_state.anchor_price_at_join,
+            CustomError::HouseJoinRequiresAnchoredPrice
+        );
+
+        // The pool's stake is paid back into its own vault, so it can't join games that
+        // settle into internal balances
+        require!(!game_state.balance_escrow, CustomError::InvalidEscrow);
+
+        // The pool's exposure is tracked per market, so the game must use its own feed
+        require!(
+            ctx.accounts.chainlink_feed.key() == game_state.price_feed,
+            CustomError::InvalidPriceFeed
+        );
+
+        // Check the join against the current price from the game's price sources
+        let current_price = get_price(
+            &ctx.accounts.chainlink_program,
+            &ctx.accounts.chainlink_feed,
+            game_state.price_sources.as_ref(),
+            ctx.remaining_accounts,
+            current_time,
+        )?;
+
+        game_state.validate_join_price(current_price)?;
+        game_state.anchor_join_price(current_price);
+
+        let challenger_prediction = game_state.get_challenger_prediction();
+        let entry_amount = game_state.entry_amount;
+
+        house_pool.lock_exposure(game_state.price_feed, challenger_prediction, entry_amount)?;
+
+        game_state.challenger = Some(house_pool_key);
+        game_state.challenger_holder = game_state.challenger;
+        game_state.started_at = Some(current_time);
+        game_state.status = GameStatus::Active;
+        game_state.house_pool = Some(house_pool_key);
+
+        // Snapshot both sides' ratings so settlement doesn't depend on later games
+        let initiator_profile = &mut ctx.accounts.initiator_profile;
+        initiator_profile.initialize(
+            initiator,
+            game_state.stake_mint,
+            ctx.bumps.initiator_profile,
+        );
+        game_state.initiator_rating = initiator_profile.rating;
+
+        let house_pool_profile = &mut ctx.accounts.house_pool_profile;
+        house_pool_profile.initialize(
+            house_pool_key,
+            game_state.stake_mint,
+            ctx.bumps.house_pool_profile,
+        );
+        game_state.challenger_rating = house_pool_profile.rating;
+
+        game_state.lock_escrow(entry_amount, ctx.accounts.shared_escrow.as_deref_mut())?;
+
+        // Stake from the pool's vault, covering any transfer fee so the game's vault
+        // receives the entry amount in full
+        let pool_seeds = &[
+            b"house_pool".as_ref(),
+            house_pool.stake_mint.as_ref(),
+            &[house_pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        transfer_from_vault(
+            &ctx.accounts.house_pool_vault.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.stake_mint,
+            &house_pool.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            pool_signer,
+            get_gross_transfer_amount(&ctx.accounts.stake_mint.to_account_info(), entry_amount)?,
+        )?;
+
+        ctx.accounts.vault.reload()?;
+        game_state.validate_escrow(
+            ctx.accounts.vault.amount,
+            ctx.accounts.shared_escrow.as_deref(),
+        )?;
+
+        // Mint a single position token for each side and fix the supply
+        let initiator_position_mint = ctx.accounts.initiator_position_mint.key();
+        let challenger_position_mint = ctx.accounts.challenger_position_mint.key();
+
+        game_state.initiator_position_mint = Some(initiator_position_mint);
+        game_state.challenger_position_mint = Some(challenger_position_mint);
+        game_state.join_rent_payer = Some(ctx.accounts.payer.key());
+
+        let seeds = &[
+            b"game_state",
+            initiator.as_ref(),
+            &game_id.to_le_bytes(),
+            &[game_state.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        mint_position_tokens(
+            [
+                (
+                    ctx.accounts.initiator_position_mint.to_account_info(),
+                    ctx.accounts.initiator_position_account.to_account_info(),
+                ),
+                (
+                    ctx.accounts.challenger_position_mint.to_account_info(),
+                    ctx.accounts.challenger_position_account.to_account_info(),
+                ),
+            ],
+            &game_state.to_account_info(),
+            &ctx.accounts.position_token_program.to_account_info(),
+            signer,
+        )?;
+
+        emit!(GameJoined {
+            game_id,
+            status: GameStatus::Active,
+            challenger: house_pool_key,
+            challenger_prediction,
+            initial_price: game_state.initial_price,
+            initiator_position_mint,
+            challenger_position_mint,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Authorizes an ephemeral session key to sign game actions on the owner's behalf.
+    ///
+    /// The session PDA is approved as delegate on the owner's stake token account for
+    /// `max_stake`, so stakes are spent without a wallet signature. Payouts still only go to
+    /// the owner's token accounts. A token account has a single delegate, so a new session
+    /// for the same mint replaces the previous session's allowance.
+    ///
+    /// This function:
+    /// - Validates the expiry and allowed actions
+    /// - Wraps `max_stake` lamports up front for SOL stakes
+    /// - Delegates up to `max_stake` of the owner's tokens to the session
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_signer: Pubkey,
+        max_stake: u64,
+        expires_at: i64,
+        allowed_actions: u8,
+    ) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+        let owner_key = ctx.accounts.owner.key();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let max_expires_at = current_time
+            .checked_add(MAX_SESSION_DURATION_SECONDS)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        require!(
+            expires_at > current_time && expires_at <= max_expires_at,
+            CustomError::InvalidSession
+        );
+        require!(
+            allowed_actions != 0 && allowed_actions & !SESSION_ACTIONS_ALL == 0,
+            CustomError::InvalidSession
+        );
+        require!(session_signer != owner_key, CustomError::InvalidSession);
+
+        session.owner = owner_key;
+        session.session_signer = session_signer;
+        session.stake_mint = ctx.accounts.stake_mint.key();
+        session.max_stake = max_stake;
+        session.staked_amount = 0;
+        session.expires_at = expires_at;
+        session.allowed_actions = allowed_actions;
+        session.rent_payer = ctx.accounts.payer.key();
+        session.bump = ctx.bumps.session;
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+
+        // SOL stakes are wrapped now, since a session key can't spend lamports from the wallet
+        if is_native_mint(&session.stake_mint) {
+            wrap_sol(
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.owner_token_account.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+                max_stake,
+            )?;
+        }
+
+        let cpi_accounts = Approve {
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            delegate: session.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        token_interface::approve(cpi_ctx, max_stake)?;
+
+        emit!(SessionCreated {
+            owner: owner_key,
+            session_signer,
+            stake_mint: session.stake_mint,
+            max_stake,
+            expires_at,
+            allowed_actions,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Revokes a session key and its token delegation, closing the session account.
+    ///
+    /// For SOL stakes, the part of the allowance the session didn't stake is unwrapped back
+    /// to the owner's wallet. Any other wrapped SOL in their token account stays wrapped.
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        let session = &ctx.accounts.session;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Revoke {
+            source: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program.clone(), cpi_accounts);
+
+        token_interface::revoke(cpi_ctx)?;
+
+        if is_native_mint(&session.stake_mint) {
+            let unused_allowance = session
+                .max_stake
+                .saturating_sub(session.staked_amount)
+                .min(ctx.accounts.owner_token_account.amount);
+
+            unwrap_sol(
+                &ctx.accounts.owner_token_account.to_account_info(),
+                &ctx.accounts.stake_mint,
+                &ctx.accounts.owner.to_account_info(),
+                &[],
+                &ctx.accounts.sol_payout.to_account_info(),
+                ctx.bumps.sol_payout,
+                &ctx.accounts.owner.to_account_info(),
+                &[(ctx.accounts.owner.to_account_info(), unused_allowance)],
+                &ctx.accounts.system_program.to_account_info(),
+                &cpi_program,
+            )?;
+        }
+
+        emit!(SessionRevoked {
+            owner: session.owner,
+            session_signer: session.session_signer,
             timestamp: current_time,
         });
 
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedMint {
+    pub mint: Pubkey,           // Token mint accepted for stakes
+    pub entry_amount: u64,      // Stake per player, in the mint's base units
+    pub enabled: bool,          // Whether new games may be created with this mint
+    pub season: Option<Pubkey>, // Latest season created for the mint
+    pub bump: u8,
+}
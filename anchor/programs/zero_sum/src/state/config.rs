@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,           // Authority allowed to maintain protocol settings
+    pub treasury: Pubkey,        // Receives protocol fees not allocated elsewhere
+    pub protocol_fee_bps: u16,   // Fee taken from each completed game's payout
+    pub season_share_bps: u16,   // Share of the protocol fee paid into the active season's pool
+    pub referral_share_bps: u16, // Share of each player's half of the fee paid to their referrer
+    pub bump: u8,
+}
@@ -1,22 +1,51 @@
 use anchor_lang::prelude::*;
 
-use crate::common::{CustomError, GameStatus, PricePrediction, GAME_TIMEOUT_SECONDS};
+use super::{PriceSources, SharedEscrow};
+use crate::common::{
+    get_rating_delta, has_price_moved_by_percentage, CustomError, GameStatus, PricePrediction,
+    TimeoutPolicy, WinCondition, BPS_DENOMINATOR, GAME_TIMEOUT_SECONDS, MAX_JOIN_PRICE_MOVEMENT,
+    MAX_ROUND_PROOF_AGE_SECONDS, START_GAME_WINDOW_SECONDS,
+};
 
 #[account]
 #[derive(InitSpace)]
 pub struct GameState {
     pub game_id: u64,                                // Unique identifier for the game
-    pub initiator: Pubkey,                           // Game creator address
+    pub initiator: Pubkey,                           // Game creator address (used in PDA seeds)
+    pub initiator_holder: Pubkey,                    // Current holder of the initiator's side
     pub initiator_prediction: PricePrediction,       // First player's prediction
     pub challenger: Option<Pubkey>,                  // Joining player address
+    pub challenger_holder: Option<Pubkey>,           // Current holder of the challenger's side
     pub winning_prediction: Option<PricePrediction>, // Winning player's prediction
-    pub entry_amount: u64,                           // Amount to enter the game (in USDC)
+    pub stake_mint: Pubkey,                          // Token mint used for stakes
+    pub entry_amount: u64,                           // Amount to enter the game (in base units)
     pub initial_price: f64,                          // Price at game creation
     pub final_price: Option<f64>,                    // Price at game completion
     pub created_at: i64,                             // Timestamp of game creation
+    pub refresh_nonce: u32, // Times the game has been refreshed; joins must expect the latest
+    pub start_at: Option<i64>, // Scheduled time to capture the initial price; cleared once captured
+    pub anchor_price_at_join: bool, // Initial price is taken again when a challenger joins
+    pub timeout_policy: TimeoutPolicy, // How the pot is split if the game times out
+    pub increase_threshold_bps: u16, // Rise the Increase side needs to win
+    pub decrease_threshold_bps: u16, // Fall the Decrease side needs to win
+    pub win_condition: WinCondition, // Whether the threshold must be sustained to win
     pub started_at: Option<i64>, // Timestamp when challenger joined and game started
-    pub closed_at: Option<i64>,  // Timestamp when game was closed
-    pub status: GameStatus,      // Status of the game
+    pub closed_at: Option<i64>, // Timestamp when game was closed
+    pub initiator_position_mint: Option<Pubkey>, // Position token for the initiator's side
+    pub challenger_position_mint: Option<Pubkey>, // Position token for the challenger's side
+    pub status: GameStatus, // Status of the game
+    pub vault: Pubkey,      // Token account holding the game's stakes
+    pub escrow_amount: u64, // Stakes currently held in escrow for the game
+    pub shared_escrow: bool, // Stakes sit in the mint's shared escrow vault rather than the game's own
+    pub balance_escrow: bool, // Stakes sit in the mint's balance vault, settled into internal balances
+    pub price_feed: Pubkey,   // Chainlink feed the game is priced with
+    pub price_sources: Option<PriceSources>, // Market's price sources when the game was created
+    pub house_pool: Option<Pubkey>, // House pool that joined as the challenger, if any
+    pub reserved_challenger: Option<Pubkey>, // Only this player may join, e.g. a tournament opponent
+    pub initiator_rating: u32,               // Initiator's rating when the game started
+    pub challenger_rating: u32,              // Challenger's rating when the game started
+    pub rent_payer: Pubkey,                  // Paid the game's rent; refunded when it closes
+    pub join_rent_payer: Option<Pubkey>,     // Paid the position mints' rent
     pub bump: u8,
 }
 
@@ -31,19 +60,32 @@ impl GameState {
         self.closed_at.is_some()
     }
 
+    /// Returns true if the game is a tournament match. These stay open after settling until
+    /// the tournament reads their result.
+    pub fn is_tournament_game(&self) -> bool {
+        self.reserved_challenger.is_some()
+    }
+
     /// Returns true if the game has no challenger.
     pub fn joinable_game(&self) -> bool {
         self.challenger.is_none()
     }
 
-    /// Returns true if the given public key is the initiator.
-    pub fn is_initiator(&self, pubkey: Pubkey) -> bool {
+    /// Returns true if the given public key created the game.
+    ///
+    /// This is the address used in the game's PDA seeds and never changes.
+    pub fn is_creator(&self, pubkey: Pubkey) -> bool {
         pubkey == self.initiator
     }
 
-    /// Returns true if the given public key matches the challenger.
+    /// Returns true if the given public key currently holds the initiator's side.
+    pub fn is_initiator(&self, pubkey: Pubkey) -> bool {
+        pubkey == self.initiator_holder
+    }
+
+    /// Returns true if the given public key currently holds the challenger's side.
     pub fn is_challenger(&self, pubkey: Pubkey) -> bool {
-        Some(pubkey) == self.challenger
+        Some(pubkey) == self.challenger_holder
     }
 
     /// Returns true if the given public key is a participant in the game.
@@ -56,32 +98,157 @@ impl GameState {
         game_id == self.game_id
     }
 
-    /// Validates whether a player can join the game.
+    /// Validates whether a player can join the game. Scheduled games can't be joined once
+    /// their start window has passed.
     pub fn validate_join(
         &self,
         game_id: u64,
         challenger_key: Pubkey,
         initiator_key: Pubkey,
+        current_time: i64,
     ) -> Result<()> {
         require!(
             self.is_correct_game_id(game_id),
             CustomError::IncorrectGameId
         );
         require!(
-            self.is_initiator(initiator_key),
+            self.is_creator(initiator_key),
             CustomError::IncorrectInitiator
         );
         require!(!self.is_closed(), CustomError::GameAlreadyEnded);
         require!(
-            !self.is_initiator(challenger_key),
+            !self.is_creator(challenger_key) && !self.is_initiator(challenger_key),
             CustomError::CannotJoinOwnGame
         );
         require!(self.joinable_game(), CustomError::GameAlreadyFull);
+        require!(
+            !self.is_start_missed(current_time),
+            CustomError::StartWindowMissed
+        );
+        require!(
+            self.reserved_challenger.is_none() || self.reserved_challenger == Some(challenger_key),
+            CustomError::NotReservedChallenger
+        );
+
+        Ok(())
+    }
+
+    /// Validates that the game hasn't been refreshed since the joiner last read it, so it is
+    /// joined at the initial price and creation time they saw.
+    pub fn validate_refresh_nonce(&self, expected_refresh_nonce: u32) -> Result<()> {
+        require!(
+            self.refresh_nonce == expected_refresh_nonce,
+            CustomError::UnexpectedRefreshNonce
+        );
+
+        Ok(())
+    }
+
+    /// Validates that another pending game takes the opposite side of the same bet, so the
+    /// two can be merged into one game. Tournament games are only played against their
+    /// reserved opponent, so neither game can be one.
+    pub fn validate_match(&self, other: &GameState) -> Result<()> {
+        require!(
+            !self.is_tournament_game() && !other.is_tournament_game(),
+            CustomError::CannotMatchTournamentGame
+        );
+        require!(
+            self.stake_mint == other.stake_mint
+                && self.entry_amount == other.entry_amount
+                && self.price_feed == other.price_feed
+                && self.price_sources == other.price_sources
+                && self.balance_escrow == other.balance_escrow
+                && self.start_at == other.start_at
+                && self.anchor_price_at_join == other.anchor_price_at_join
+                && self.timeout_policy == other.timeout_policy
+                && self.increase_threshold_bps == other.increase_threshold_bps
+                && self.decrease_threshold_bps == other.decrease_threshold_bps
+                && self.win_condition == other.win_condition
+                && self.initiator_prediction != other.initiator_prediction,
+            CustomError::IncompatibleGames
+        );
+
+        Ok(())
+    }
+
+    /// Validates that the current price is close enough to the initial price for the game
+    /// to be joined. Scheduled games have no initial price to drift from until they start,
+    /// and games anchored at join take the current price instead.
+    pub fn validate_join_price(&self, current_price: f64) -> Result<()> {
+        if self.is_awaiting_start() || self.anchor_price_at_join {
+            return Ok(());
+        }
+
+        let (threshold_exceeded, _, _) = has_price_moved_by_percentage(
+            self.initial_price,
+            current_price,
+            MAX_JOIN_PRICE_MOVEMENT,
+        )?;
+
+        require!(!threshold_exceeded, CustomError::ExcessivePriceVolatility);
 
         Ok(())
     }
 
-    /// Validates whether a player can close the game.
+    /// Validates the challenger's slippage limits: that the initial price is the one they
+    /// expect, and that the current price hasn't moved against their side from it by more
+    /// than they accept. Games without an initial price yet, or anchored at join, start
+    /// from the current price so have no adverse drift.
+    pub fn validate_challenger_slippage(
+        &self,
+        current_price: f64,
+        expected_initial_price: Option<f64>,
+        max_adverse_drift_bps: Option<u16>,
+    ) -> Result<()> {
+        if let Some(expected_initial_price) = expected_initial_price {
+            require!(
+                self.initial_price == expected_initial_price,
+                CustomError::UnexpectedInitialPrice
+            );
+        }
+
+        if let Some(max_adverse_drift_bps) = max_adverse_drift_bps {
+            if self.is_awaiting_start() || self.anchor_price_at_join {
+                return Ok(());
+            }
+
+            // Movement in the challenger's favour counts as no drift
+            let price_change = (current_price - self.initial_price) / self.initial_price;
+            let adverse_drift = match self.get_challenger_prediction() {
+                PricePrediction::Increase => -price_change,
+                PricePrediction::Decrease => price_change,
+            };
+
+            require!(
+                adverse_drift * BPS_DENOMINATOR as f64 <= max_adverse_drift_bps as f64,
+                CustomError::ExcessiveAdverseDrift
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Re-anchors the initial price to the price at join for games created that way.
+    /// Scheduled games keep waiting for their start price instead.
+    pub fn anchor_join_price(&mut self, current_price: f64) {
+        if self.anchor_price_at_join && !self.is_awaiting_start() {
+            self.initial_price = current_price;
+        }
+    }
+
+    /// Returns true if the game is scheduled and hasn't captured its initial price yet.
+    pub fn is_awaiting_start(&self) -> bool {
+        self.start_at.is_some()
+    }
+
+    /// Returns true if the game is scheduled and its start window passed without its
+    /// initial price being captured.
+    pub fn is_start_missed(&self, current_time: i64) -> bool {
+        self.start_at
+            .is_some_and(|start_at| current_time > start_at + START_GAME_WINDOW_SECONDS)
+    }
+
+    /// Validates whether the game can be closed.
     pub fn validate_close(
         &self,
         game_id: u64,
@@ -93,15 +260,85 @@ impl GameState {
             CustomError::IncorrectGameId
         );
         require!(self.is_active(), CustomError::GameNotActive);
+        require!(!self.is_awaiting_start(), CustomError::GameNotStarted);
         require!(self.is_player(player_key), CustomError::NotAuthorized);
         require!(
-            self.is_initiator(initiator_key),
+            self.is_creator(initiator_key),
             CustomError::IncorrectInitiator
         );
 
         Ok(())
     }
 
+    /// Validates whether the game can be drawn: once it has timed out, or once it has
+    /// missed its scheduled start and can only be refunded.
+    pub fn validate_draw(
+        &self,
+        game_id: u64,
+        player_key: Pubkey,
+        initiator_key: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        if !self.is_start_missed(current_time) {
+            self.validate_close(game_id, player_key, initiator_key)?;
+
+            require!(
+                self.is_timed_out(current_time)?,
+                CustomError::GameTimeoutNotReached
+            );
+
+            return Ok(());
+        }
+
+        require!(
+            self.is_correct_game_id(game_id),
+            CustomError::IncorrectGameId
+        );
+        require!(self.is_active(), CustomError::GameNotActive);
+        require!(self.is_player(player_key), CustomError::NotAuthorized);
+        require!(
+            self.is_creator(initiator_key),
+            CustomError::IncorrectInitiator
+        );
+
+        Ok(())
+    }
+
+    /// Validates whether a player can transfer their side to a new holder.
+    pub fn validate_transfer(
+        &self,
+        game_id: u64,
+        holder_key: Pubkey,
+        new_holder_key: Pubkey,
+        initiator_key: Pubkey,
+    ) -> Result<()> {
+        require!(
+            self.is_correct_game_id(game_id),
+            CustomError::IncorrectGameId
+        );
+        require!(self.is_active(), CustomError::GameNotActive);
+        require!(self.is_player(holder_key), CustomError::NotAuthorized);
+        require!(
+            !self.is_player(new_holder_key),
+            CustomError::InvalidPositionRecipient
+        );
+        require!(
+            self.is_creator(initiator_key),
+            CustomError::IncorrectInitiator
+        );
+
+        Ok(())
+    }
+
+    /// Assigns the side currently held by the given holder to a new holder.
+    pub fn set_holder(&mut self, holder_key: Pubkey, new_holder_key: Pubkey) {
+        if self.is_initiator(holder_key) {
+            self.initiator_holder = new_holder_key;
+        } else {
+            self.challenger_holder = Some(new_holder_key);
+        }
+    }
+
     /// Validates whether the initiator can withdraw from an open game.
     pub fn validate_withdraw(&self, game_id: u64, initiator_key: Pubkey) -> Result<()> {
         require!(
@@ -115,6 +352,277 @@ impl GameState {
         Ok(())
     }
 
+    /// Validates whether the initiator can re-anchor the game to the current price.
+    ///
+    /// Only open games that have captured their initial price can be refreshed.
+    pub fn validate_refresh(&self, game_id: u64, initiator_key: Pubkey) -> Result<()> {
+        require!(
+            self.is_correct_game_id(game_id),
+            CustomError::IncorrectGameId
+        );
+        require!(self.is_initiator(initiator_key), CustomError::NotInitiator);
+        require!(!self.is_closed(), CustomError::GameAlreadyEnded);
+        require!(self.joinable_game(), CustomError::GameAlreadyFull);
+        require!(!self.is_awaiting_start(), CustomError::GameNotStarted);
+
+        Ok(())
+    }
+
+    /// Returns the winning prediction and the percentage price movement, once the price
+    /// has moved past the win threshold.
+    pub fn get_winning_prediction(&self, final_price: f64) -> Result<(PricePrediction, f64)> {
+        // Each direction is tested against its own threshold
+        let threshold = self.get_win_threshold(final_price >= self.initial_price);
+        let (threshold_exceeded, direction, price_movement_percentage) =
+            has_price_moved_by_percentage(self.initial_price, final_price, threshold)?;
+
+        require!(threshold_exceeded, CustomError::ThresholdNotReached);
+
+        let winning_prediction = if direction == 1 {
+            PricePrediction::Increase
+        } else {
+            PricePrediction::Decrease
+        };
+
+        Ok((winning_prediction, price_movement_percentage))
+    }
+
+    /// Returns the price to test the win threshold against for a sustained win condition,
+    /// given consecutive rounds of the feed as (price, timestamp) pairs, oldest first. The
+    /// last round must be no older than `MAX_ROUND_PROOF_AGE_SECONDS`, so the win held
+    /// recently without the proof having to end at the feed's latest round.
+    ///
+    /// Consecutive rounds use the round that moved least from the initial price, provided
+    /// they all moved the same way. Time-weighted games use the average price over the
+    /// window ending at the last round, which must have started after the game did.
+    pub fn get_sustained_price(&self, rounds: &[(f64, i64)], current_time: i64) -> Result<f64> {
+        let started_at = self.started_at.ok_or(error!(CustomError::GameNotActive))?;
+        let (&(first_price, first_timestamp), &(_, last_timestamp)) = rounds
+            .first()
+            .zip(rounds.last())
+            .ok_or(error!(CustomError::InvalidRoundProof))?;
+
+        require!(
+            current_time - last_timestamp <= MAX_ROUND_PROOF_AGE_SECONDS,
+            CustomError::InvalidRoundProof
+        );
+
+        match self.win_condition {
+            WinCondition::Instant => err!(CustomError::InvalidWinCondition),
+            WinCondition::ConsecutiveRounds {
+                rounds: required_rounds,
+            } => {
+                require!(
+                    rounds.len() == required_rounds as usize && first_timestamp >= started_at,
+                    CustomError::InvalidRoundProof
+                );
+
+                let increase = first_price >= self.initial_price;
+                let mut sustained_price = first_price;
+
+                for &(price, _) in rounds {
+                    require!(
+                        (price >= self.initial_price) == increase,
+                        CustomError::ThresholdNotReached
+                    );
+
+                    if (price - self.initial_price).abs()
+                        < (sustained_price - self.initial_price).abs()
+                    {
+                        sustained_price = price;
+                    }
+                }
+
+                Ok(sustained_price)
+            }
+            WinCondition::TimeWeighted { window_seconds } => {
+                let window_start = last_timestamp
+                    .checked_sub(window_seconds as i64)
+                    .ok_or(error!(CustomError::Overflow))?;
+
+                // The whole window must fall within the game
+                require!(window_start >= started_at, CustomError::ThresholdNotReached);
+                require!(
+                    first_timestamp <= window_start,
+                    CustomError::InvalidRoundProof
+                );
+
+                // Weight each round's price by how long it stood within the window
+                let mut weighted_price = 0.0;
+                let mut total_duration = 0;
+
+                for (i, &(price, timestamp)) in rounds.iter().enumerate() {
+                    let from = timestamp.max(window_start);
+                    let to = rounds
+                        .get(i + 1)
+                        .map_or(last_timestamp, |&(_, next_timestamp)| next_timestamp)
+                        .max(from);
+
+                    weighted_price += price * (to - from) as f64;
+                    total_duration += to - from;
+                }
+
+                require!(total_duration > 0, CustomError::InvalidRoundProof);
+
+                Ok(weighted_price / total_duration as f64)
+            }
+        }
+    }
+
+    /// Returns the fractional price movement needed to win in the given direction.
+    pub fn get_win_threshold(&self, increase: bool) -> f64 {
+        let threshold_bps = if increase {
+            self.increase_threshold_bps
+        } else {
+            self.decrease_threshold_bps
+        };
+
+        threshold_bps as f64 / BPS_DENOMINATOR as f64
+    }
+
+    /// Returns the share of the pot paid to the given side of a timed-out game, split by
+    /// the game's timeout policy using its final price. Games drawn without a final price,
+    /// such as during an oracle outage, refund each side's entry amount.
+    pub fn get_timeout_payout(&self, prediction: PricePrediction) -> Result<u64> {
+        let total_pot = self
+            .entry_amount
+            .checked_mul(2)
+            .ok_or(error!(CustomError::Overflow))?;
+        let progress = self.get_timeout_progress();
+
+        let increase_payout = match self.timeout_policy {
+            TimeoutPolicy::Proportional => {
+                ((self.entry_amount as f64 * (1.0 + progress)) as u64).min(total_pot)
+            }
+            _ if progress > 0.0 => total_pot,
+            _ if progress < 0.0 => 0,
+            _ => self.entry_amount,
+        };
+
+        Ok(match prediction {
+            PricePrediction::Increase => increase_payout,
+            PricePrediction::Decrease => total_pot - increase_payout,
+        })
+    }
+
+    /// Returns the initiator's score for rating a timed-out game, matching the share of the
+    /// pot their side is paid. An even split scores half a point for each side.
+    pub fn get_timeout_score(&self) -> f64 {
+        let increase_score = (1.0 + self.get_timeout_progress()) / 2.0;
+
+        match self.initiator_prediction {
+            PricePrediction::Increase => increase_score,
+            PricePrediction::Decrease => 1.0 - increase_score,
+        }
+    }
+
+    /// Returns how far a timed-out game's final price moved towards either side under its
+    /// timeout policy, from -1 for all the way to Decrease to 1 for all the way to Increase.
+    /// Games refunded in full make no progress either way.
+    fn get_timeout_progress(&self) -> f64 {
+        let Some(final_price) = self.final_price else {
+            return 0.0;
+        };
+
+        let price_change = (final_price - self.initial_price) / self.initial_price;
+
+        match self.timeout_policy {
+            TimeoutPolicy::Refund => 0.0,
+            TimeoutPolicy::Directional => {
+                if price_change > 0.0 {
+                    1.0
+                } else if price_change < 0.0 {
+                    -1.0
+                } else {
+                    0.0
+                }
+            }
+            TimeoutPolicy::Proportional => {
+                // Movement is measured against the threshold of the side it favours
+                let threshold = self.get_win_threshold(price_change >= 0.0);
+                (price_change / threshold).clamp(-1.0, 1.0)
+            }
+        }
+    }
+
+    /// Returns the prediction made by the given player.
+    pub fn get_player_prediction(&self, player: Pubkey) -> Result<PricePrediction> {
+        if self.is_initiator(player) {
+            Ok(self.initiator_prediction)
+        } else if self.is_challenger(player) {
+            Ok(self.get_challenger_prediction())
+        } else {
+            err!(CustomError::NotAuthorized)
+        }
+    }
+
+    /// Returns the position token mint backing the side held by the given player.
+    pub fn get_player_position_mint(&self, player: Pubkey) -> Result<Pubkey> {
+        let position_mint = if self.is_initiator(player) {
+            self.initiator_position_mint
+        } else if self.is_challenger(player) {
+            self.challenger_position_mint
+        } else {
+            None
+        };
+
+        position_mint.ok_or(error!(CustomError::InvalidPositionToken))
+    }
+
+    /// Returns the holder of the side backed by the given position token mint.
+    pub fn get_position_holder(&self, mint: Pubkey) -> Result<Pubkey> {
+        let holder = if self.initiator_position_mint == Some(mint) {
+            Some(self.initiator_holder)
+        } else if self.challenger_position_mint == Some(mint) {
+            self.challenger_holder
+        } else {
+            None
+        };
+
+        holder.ok_or(error!(CustomError::InvalidPositionToken))
+    }
+
+    /// Returns the side backed by the given position token mint.
+    pub fn get_position_prediction(&self, mint: Pubkey) -> Result<PricePrediction> {
+        if self.initiator_position_mint == Some(mint) {
+            Ok(self.initiator_prediction)
+        } else if self.challenger_position_mint == Some(mint) {
+            Ok(self.get_challenger_prediction())
+        } else {
+            err!(CustomError::InvalidPositionToken)
+        }
+    }
+
+    /// Returns the rating changes for the initiator and challenger given the initiator's
+    /// score, using the ratings both players had when the game started.
+    pub fn get_rating_deltas(&self, initiator_score: f64) -> (i32, i32) {
+        (
+            get_rating_delta(
+                self.initiator_rating,
+                self.challenger_rating,
+                initiator_score,
+            ),
+            get_rating_delta(
+                self.challenger_rating,
+                self.initiator_rating,
+                1.0 - initiator_score,
+            ),
+        )
+    }
+
+    /// Returns the position mint of the side opposing the given position mint.
+    pub fn get_opposing_position_mint(&self, mint: Pubkey) -> Result<Pubkey> {
+        let opposing_mint = if self.initiator_position_mint == Some(mint) {
+            self.challenger_position_mint
+        } else if self.challenger_position_mint == Some(mint) {
+            self.initiator_position_mint
+        } else {
+            None
+        };
+
+        opposing_mint.ok_or(error!(CustomError::InvalidPositionToken))
+    }
+
     /// Returns the prediction for the challenger (opposite of initiator's).
     pub fn get_challenger_prediction(&self) -> PricePrediction {
         match self.initiator_prediction {
@@ -123,6 +631,75 @@ impl GameState {
         }
     }
 
+    /// Returns true if the stakes sit in a vault of the game's own, closed once emptied.
+    pub fn has_own_vault(&self) -> bool {
+        !self.shared_escrow && !self.balance_escrow
+    }
+
+    /// Records stakes moved into the game's vault. Shared escrow games also add them to
+    /// the mint's escrowed total, so the shared escrow must be given exactly for those.
+    pub fn lock_escrow(
+        &mut self,
+        amount: u64,
+        shared_escrow: Option<&mut SharedEscrow>,
+    ) -> Result<()> {
+        require!(
+            self.shared_escrow == shared_escrow.is_some(),
+            CustomError::InvalidEscrow
+        );
+
+        self.escrow_amount = self
+            .escrow_amount
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        if let Some(shared_escrow) = shared_escrow {
+            shared_escrow.lock(amount)?;
+        }
+
+        Ok(())
+    }
+
+    /// Records stakes paid out of the game's vault.
+    pub fn release_escrow(
+        &mut self,
+        amount: u64,
+        shared_escrow: Option<&mut SharedEscrow>,
+    ) -> Result<()> {
+        require!(
+            self.shared_escrow == shared_escrow.is_some(),
+            CustomError::InvalidEscrow
+        );
+
+        self.escrow_amount = self
+            .escrow_amount
+            .checked_sub(amount)
+            .ok_or(error!(CustomError::EscrowInvariantViolated))?;
+
+        if let Some(shared_escrow) = shared_escrow {
+            shared_escrow.release(amount)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the vault holds at least the stakes escrowed in it: the game's own
+    /// escrow for a game vault, or the sum of all games' escrows for a shared vault.
+    pub fn validate_escrow(
+        &self,
+        vault_amount: u64,
+        shared_escrow: Option<&SharedEscrow>,
+    ) -> Result<()> {
+        let escrowed = shared_escrow.map_or(self.escrow_amount, |escrow| escrow.total_escrowed);
+
+        require!(
+            escrowed <= vault_amount,
+            CustomError::EscrowInvariantViolated
+        );
+
+        Ok(())
+    }
+
     /// Returns true if the game has timed out without being resolved.
     pub fn is_timed_out(&self, current_time: i64) -> Result<bool> {
         // If the game is not active, it's not timed out
@@ -130,12 +707,15 @@ impl GameState {
             return Ok(false);
         }
 
-        // If game has a challenger (active game), check against started_at
-        let start_time = self.started_at.unwrap();
-        let timeout_time = start_time
-            .checked_add(GAME_TIMEOUT_SECONDS)
-            .ok_or(error!(CustomError::Overflow))?;
+        Ok(current_time > self.get_timeout_time()?)
+    }
+
+    /// Returns the time the game times out, measured from when it started.
+    pub fn get_timeout_time(&self) -> Result<i64> {
+        let start_time = self.started_at.ok_or(error!(CustomError::GameNotActive))?;
 
-        return Ok(current_time > timeout_time);
+        start_time
+            .checked_add(GAME_TIMEOUT_SECONDS)
+            .ok_or(error!(CustomError::Overflow))
     }
 }
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+use crate::common::{
+    CustomError, PricePrediction, HOUSE_POOL_LOCKED_SHARES, MAX_HOUSE_POOL_MARKETS,
+};
+
+#[account]
+#[derive(InitSpace)]
+pub struct HousePool {
+    pub stake_mint: Pubkey,          // Token mint the pool provides liquidity in
+    pub share_mint: Pubkey,          // Mint of the share tokens issued to liquidity providers
+    pub max_market_exposure: u64,    // Most the pool may stake in one market's active games
+    pub max_direction_exposure: u64, // Most the pool may stake on one direction across markets
+    pub increase_exposure: u64,      // Staked in active games on the increase side
+    pub decrease_exposure: u64,      // Staked in active games on the decrease side
+    #[max_len(MAX_HOUSE_POOL_MARKETS)]
+    pub markets: Vec<MarketExposure>, // Stakes in active games per price feed
+    pub locked_shares: u64, // Shares issued to nobody by the first deposit, counted in the supply
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct MarketExposure {
+    pub price_feed: Pubkey,
+    pub exposure: u64,
+}
+
+impl HousePool {
+    /// Returns the pool's stakes locked in active games.
+    pub fn locked_exposure(&self) -> u64 {
+        self.increase_exposure + self.decrease_exposure
+    }
+
+    /// Returns the value backing the share tokens when pricing a deposit: the vault's free
+    /// liquidity plus the whole pot of every active game, the most its stakes can return.
+    /// New providers can't buy into games the pool is about to win at cost.
+    pub fn get_deposit_net_asset_value(&self, vault_amount: u64) -> Result<u64> {
+        self.locked_exposure()
+            .checked_mul(2)
+            .and_then(|pots| pots.checked_add(vault_amount))
+            .ok_or(error!(CustomError::Overflow))
+    }
+
+    /// Returns the value backing the share tokens when pricing a withdrawal: the vault's
+    /// free liquidity alone, valuing the stakes in active games at nothing until they
+    /// settle. Providers can't leave games the pool is about to lose at cost.
+    pub fn get_withdrawal_net_asset_value(&self, vault_amount: u64) -> u64 {
+        vault_amount
+    }
+
+    /// Returns the shares minted for a deposit at the current share price. The first
+    /// deposit locks `HOUSE_POOL_LOCKED_SHARES` of its shares for good, so the share price
+    /// can't be inflated by donating to a pool with a tiny supply.
+    pub fn issue_shares_for_deposit(
+        &mut self,
+        amount: u64,
+        vault_amount: u64,
+        share_supply: u64,
+    ) -> Result<u64> {
+        if self.locked_shares == 0 {
+            require!(
+                amount > HOUSE_POOL_LOCKED_SHARES,
+                CustomError::InvalidLiquidityAmount
+            );
+
+            self.locked_shares = HOUSE_POOL_LOCKED_SHARES;
+
+            return Ok(amount - HOUSE_POOL_LOCKED_SHARES);
+        }
+
+        let net_asset_value = self.get_deposit_net_asset_value(vault_amount)?;
+
+        // Outstanding shares lost everything, so new deposits can't be priced against them
+        require!(net_asset_value > 0, CustomError::InsufficientPoolLiquidity);
+
+        let total_shares = share_supply as u128 + self.locked_shares as u128;
+        let shares = amount as u128 * total_shares / net_asset_value as u128;
+
+        u64::try_from(shares).map_err(|_| error!(CustomError::Overflow))
+    }
+
+    /// Returns the amount paid out for redeeming shares at the current share price.
+    pub fn get_withdrawal_amount(
+        &self,
+        shares: u64,
+        vault_amount: u64,
+        share_supply: u64,
+    ) -> Result<u64> {
+        require!(
+            shares > 0 && shares <= share_supply,
+            CustomError::InvalidLiquidityAmount
+        );
+
+        let net_asset_value = self.get_withdrawal_net_asset_value(vault_amount);
+        let total_shares = share_supply as u128 + self.locked_shares as u128;
+        let amount = shares as u128 * net_asset_value as u128 / total_shares;
+
+        u64::try_from(amount).map_err(|_| error!(CustomError::Overflow))
+    }
+
+    /// Adds a stake on the given side of a market, within the pool's exposure limits.
+    pub fn lock_exposure(
+        &mut self,
+        price_feed: Pubkey,
+        prediction: PricePrediction,
+        amount: u64,
+    ) -> Result<()> {
+        let direction_exposure = match prediction {
+            PricePrediction::Increase => &mut self.increase_exposure,
+            PricePrediction::Decrease => &mut self.decrease_exposure,
+        };
+
+        *direction_exposure = direction_exposure
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        require!(
+            *direction_exposure <= self.max_direction_exposure,
+            CustomError::HouseExposureLimitExceeded
+        );
+
+        let market = match self
+            .markets
+            .iter_mut()
+            .find(|market| market.price_feed == price_feed)
+        {
+            Some(market) => market,
+            None => {
+                require!(
+                    self.markets.len() < MAX_HOUSE_POOL_MARKETS,
+                    CustomError::HouseExposureLimitExceeded
+                );
+
+                self.markets.push(MarketExposure {
+                    price_feed,
+                    exposure: 0,
+                });
+                self.markets.last_mut().unwrap()
+            }
+        };
+
+        market.exposure = market
+            .exposure
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        require!(
+            market.exposure <= self.max_market_exposure,
+            CustomError::HouseExposureLimitExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Removes a settled stake from the pool's exposure.
+    pub fn release_exposure(
+        &mut self,
+        price_feed: Pubkey,
+        prediction: PricePrediction,
+        amount: u64,
+    ) -> Result<()> {
+        let direction_exposure = match prediction {
+            PricePrediction::Increase => &mut self.increase_exposure,
+            PricePrediction::Decrease => &mut self.decrease_exposure,
+        };
+
+        *direction_exposure = direction_exposure
+            .checked_sub(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        let index = self
+            .markets
+            .iter()
+            .position(|market| market.price_feed == price_feed)
+            .ok_or(error!(CustomError::InvalidPriceFeed))?;
+
+        self.markets[index].exposure = self.markets[index]
+            .exposure
+            .checked_sub(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        // Free the slot for other markets once nothing is staked in this one
+        if self.markets[index].exposure == 0 {
+            self.markets.remove(index);
+        }
+
+        Ok(())
+    }
+}
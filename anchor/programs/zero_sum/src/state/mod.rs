@@ -1,3 +1,25 @@
+mod allowed_mint;
+mod config;
 mod game_state;
+mod house_pool;
+mod player_balance;
+mod player_profile;
+mod price_sources;
+mod referral;
+mod season;
+mod session_key;
+mod shared_escrow;
+mod tournament;
 
+pub use allowed_mint::*;
+pub use config::*;
 pub use game_state::*;
+pub use house_pool::*;
+pub use player_balance::*;
+pub use player_profile::*;
+pub use price_sources::*;
+pub use referral::*;
+pub use season::*;
+pub use session_key::*;
+pub use shared_escrow::*;
+pub use tournament::*;
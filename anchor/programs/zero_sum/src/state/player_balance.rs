@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::common::CustomError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerBalance {
+    pub owner: Pubkey,      // Wallet the balance belongs to
+    pub stake_mint: Pubkey, // Token mint the balance is held in
+    pub amount: u64,        // Available balance (in base units), held in the mint's balance vault
+    pub bump: u8,
+}
+
+impl PlayerBalance {
+    /// Sets the owner of a newly created balance. Existing balances are left untouched.
+    pub fn initialize(&mut self, owner: Pubkey, stake_mint: Pubkey, bump: u8) {
+        if self.owner == Pubkey::default() {
+            self.owner = owner;
+            self.stake_mint = stake_mint;
+            self.bump = bump;
+        }
+    }
+
+    /// Adds funds to the balance.
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        Ok(())
+    }
+
+    /// Removes funds from the balance.
+    pub fn debit(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(error!(CustomError::InsufficientBalance))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::common::{CustomError, INITIAL_RATING};
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub player: Pubkey,        // Wallet the statistics belong to
+    pub stake_mint: Pubkey,    // Token mint the volume and PnL are denominated in
+    pub games_played: u64,     // Games settled as a win, loss or draw
+    pub wins: u64,             // Games won
+    pub losses: u64,           // Games lost
+    pub draws: u64,            // Games that timed out as a draw
+    pub cancellations: u64,    // Pending games cancelled before anyone joined
+    pub total_volume: u64,     // Total amount staked in settled games (in base units)
+    pub net_pnl: i64,          // Net profit or loss from settled games (in base units)
+    pub season: Pubkey,        // Season the season points were earned in
+    pub season_points: u64,    // Points earned in that season
+    pub referral_rewards: u64, // Protocol fees earned by the player's referrer, awaiting withdrawal
+    pub rating: u32,           // Elo skill rating, moved after every completed or drawn game
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    /// Sets the owner of a newly created profile. Existing profiles are left untouched.
+    pub fn initialize(&mut self, player: Pubkey, stake_mint: Pubkey, bump: u8) {
+        if self.player == Pubkey::default() {
+            self.player = player;
+            self.stake_mint = stake_mint;
+            self.rating = INITIAL_RATING;
+            self.bump = bump;
+        }
+    }
+
+    /// Records a game won, gaining the opponent's stake.
+    pub fn record_win(&mut self, entry_amount: u64) -> Result<()> {
+        self.wins = self
+            .wins
+            .checked_add(1)
+            .ok_or(error!(CustomError::Overflow))?;
+        self.record_settlement(entry_amount, to_pnl(entry_amount)?)
+    }
+
+    /// Records a game lost, forfeiting the player's stake.
+    pub fn record_loss(&mut self, entry_amount: u64) -> Result<()> {
+        self.losses = self
+            .losses
+            .checked_add(1)
+            .ok_or(error!(CustomError::Overflow))?;
+        self.record_settlement(entry_amount, -to_pnl(entry_amount)?)
+    }
+
+    /// Records a timed-out game, gaining or losing the difference between the player's
+    /// payout and their stake. A side given the whole pot won and a side given none of it
+    /// lost; any other split is a draw.
+    pub fn record_timeout(&mut self, entry_amount: u64, share: u64, payout: u64) -> Result<()> {
+        let outcome = if share == 0 {
+            &mut self.losses
+        } else if share >= entry_amount.saturating_mul(2) {
+            &mut self.wins
+        } else {
+            &mut self.draws
+        };
+        *outcome = outcome
+            .checked_add(1)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        let pnl = to_pnl(payout)?
+            .checked_sub(to_pnl(entry_amount)?)
+            .ok_or(error!(CustomError::Overflow))?;
+        self.record_settlement(entry_amount, pnl)
+    }
+
+    /// Applies a rating change, flooring the rating at zero.
+    pub fn apply_rating_delta(&mut self, delta: i32) {
+        self.rating = self.rating.saturating_add_signed(delta);
+    }
+
+    /// Adds points earned in the given season, starting over when a new season begins.
+    /// Returns the player's points total for the season.
+    pub fn add_season_points(&mut self, season: Pubkey, points: u64) -> Result<u64> {
+        if self.season != season {
+            self.season = season;
+            self.season_points = 0;
+        }
+
+        self.season_points = self
+            .season_points
+            .checked_add(points)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        Ok(self.season_points)
+    }
+
+    /// Credits the player's referrer with a share of the protocol fee.
+    pub fn accrue_referral_rewards(&mut self, amount: u64) -> Result<()> {
+        self.referral_rewards = self
+            .referral_rewards
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        Ok(())
+    }
+
+    /// Records a pending game cancelled by the player.
+    pub fn record_cancellation(&mut self) -> Result<()> {
+        self.cancellations = self
+            .cancellations
+            .checked_add(1)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        Ok(())
+    }
+
+    fn record_settlement(&mut self, entry_amount: u64, pnl: i64) -> Result<()> {
+        self.games_played = self
+            .games_played
+            .checked_add(1)
+            .ok_or(error!(CustomError::Overflow))?;
+        self.total_volume = self
+            .total_volume
+            .checked_add(entry_amount)
+            .ok_or(error!(CustomError::Overflow))?;
+        self.net_pnl = self
+            .net_pnl
+            .checked_add(pnl)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        Ok(())
+    }
+}
+
+fn to_pnl(amount: u64) -> Result<i64> {
+    i64::try_from(amount).map_err(|_| error!(CustomError::Overflow))
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::common::{CustomError, PricePolicy, BPS_DENOMINATOR, MAX_PRICE_SOURCES};
+
+#[account]
+#[derive(InitSpace, PartialEq)]
+pub struct PriceSources {
+    pub price_feed: Pubkey, // Chainlink feed identifying the market games are created with
+    pub policy: PricePolicy, // How the sources' prices are combined
+    #[max_len(MAX_PRICE_SOURCES)]
+    pub sources: Vec<PriceSource>, // In priority order; their accounts are passed in this order
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq)]
+pub enum PriceSource {
+    /// A Chainlink feed account
+    Chainlink {
+        feed: Pubkey,
+        max_staleness_seconds: u32,
+    },
+
+    /// Pyth price updates for a feed ID, posted through the Pyth receiver program
+    Pyth {
+        feed_id: [u8; 32],
+        max_staleness_seconds: u32,
+        max_confidence_bps: u16,
+    },
+}
+
+impl PriceSources {
+    /// Validates the sources can be read and combined by the policy.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.sources.is_empty() && self.sources.len() <= MAX_PRICE_SOURCES,
+            CustomError::InvalidPriceSource
+        );
+
+        if let PricePolicy::Median { min_sources } = self.policy {
+            require!(
+                min_sources > 0 && min_sources as usize <= self.sources.len(),
+                CustomError::InvalidPriceSource
+            );
+        }
+
+        for source in &self.sources {
+            let valid = match *source {
+                PriceSource::Chainlink {
+                    max_staleness_seconds,
+                    ..
+                } => max_staleness_seconds > 0,
+                PriceSource::Pyth {
+                    max_staleness_seconds,
+                    max_confidence_bps,
+                    ..
+                } => max_staleness_seconds > 0 && max_confidence_bps <= BPS_DENOMINATOR,
+            };
+
+            require!(valid, CustomError::InvalidPriceSource);
+        }
+
+        Ok(())
+    }
+
+    /// Combines the prices read from each source, in order, by the market's policy. Sources
+    /// that were too stale or uncertain to use have no price. Returns the price and the
+    /// number of sources it was taken from.
+    pub fn combine_prices(&self, prices: &[Option<f64>]) -> Result<(f64, u8)> {
+        match self.policy {
+            PricePolicy::Fallback => prices
+                .iter()
+                .find_map(|price| price.map(|price| (price, 1)))
+                .ok_or(error!(CustomError::PriceUnavailable)),
+            PricePolicy::Median { min_sources } => {
+                let mut fresh_prices: Vec<f64> = prices.iter().flatten().copied().collect();
+
+                require!(
+                    !fresh_prices.is_empty() && fresh_prices.len() >= min_sources as usize,
+                    CustomError::PriceUnavailable
+                );
+
+                fresh_prices.sort_by(f64::total_cmp);
+
+                // The two middle prices are the same one for an odd number of sources
+                let len = fresh_prices.len();
+                let median = (fresh_prices[(len - 1) / 2] + fresh_prices[len / 2]) / 2.0;
+
+                Ok((median, fresh_prices.len() as u8))
+            }
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub player: Pubkey,     // Referred player
+    pub referrer: Pubkey,   // Earns a share of the protocol fee on the player's games
+    pub registered_at: i64, // Timestamp the referrer was registered
+    pub bump: u8,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::common::{calculate_bps_share, CustomError, MAX_SEASON_WINNERS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub season_id: u64,     // Unique identifier for the season
+    pub stake_mint: Pubkey, // Token mint of the games counted towards this season
+    pub vault: Pubkey,      // Token account holding the prize pool
+    pub start_at: i64,      // Timestamp the season opens
+    pub end_at: i64,        // Timestamp the season ends and prizes become claimable
+    pub prize_pool: u64,    // Total fees paid into the prize pool (in base units)
+    #[max_len(MAX_SEASON_WINNERS)]
+    pub payout_curve: Vec<u16>, // Share of the prize pool paid to each rank, in basis points
+    #[max_len(MAX_SEASON_WINNERS)]
+    pub leaderboard: Vec<SeasonStanding>, // Top players ordered by points, highest first
+    pub claimed_ranks: u16, // Bitmap of ranks whose prize has been claimed
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SeasonStanding {
+    pub player: Pubkey,
+    pub points: u64,
+}
+
+impl Season {
+    /// Returns true if games settled at the given time count towards the season.
+    pub fn is_active(&self, current_time: i64) -> bool {
+        current_time >= self.start_at && current_time < self.end_at
+    }
+
+    /// Returns true if the season is over and prizes can be claimed.
+    pub fn is_ended(&self, current_time: i64) -> bool {
+        current_time >= self.end_at
+    }
+
+    /// Updates a player's standing with their new season points total.
+    ///
+    /// Only the top `payout_curve.len()` players are kept. Ties keep the player who
+    /// reached the score first ahead.
+    pub fn record_points(&mut self, player: Pubkey, points: u64) {
+        if let Some(standing) = self.leaderboard.iter_mut().find(|s| s.player == player) {
+            standing.points = points;
+        } else if self.leaderboard.len() < self.payout_curve.len() {
+            self.leaderboard.push(SeasonStanding { player, points });
+        } else if self.leaderboard.last().is_some_and(|s| points > s.points) {
+            self.leaderboard.pop();
+            self.leaderboard.push(SeasonStanding { player, points });
+        } else {
+            return;
+        }
+
+        self.leaderboard
+            .sort_by_key(|standing| std::cmp::Reverse(standing.points));
+    }
+
+    /// Returns the player's leaderboard rank, starting from zero.
+    pub fn get_rank(&self, player: Pubkey) -> Option<usize> {
+        self.leaderboard.iter().position(|s| s.player == player)
+    }
+
+    /// Returns true if the prize for the given rank has been claimed.
+    pub fn is_rank_claimed(&self, rank: usize) -> bool {
+        self.claimed_ranks & (1 << rank) != 0
+    }
+
+    /// Marks the prize for the given rank as claimed and returns its amount.
+    pub fn claim_prize(&mut self, rank: usize) -> Result<u64> {
+        require!(
+            !self.is_rank_claimed(rank),
+            CustomError::PrizeAlreadyClaimed
+        );
+
+        self.claimed_ranks |= 1 << rank;
+
+        calculate_bps_share(self.prize_pool, self.payout_curve[rank])
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::common::CustomError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SessionKey {
+    pub owner: Pubkey,          // Wallet the session acts on behalf of
+    pub session_signer: Pubkey, // Ephemeral key allowed to sign game actions
+    pub stake_mint: Pubkey,     // Token mint the session may stake
+    pub max_stake: u64,         // Maximum total amount the session may stake
+    pub staked_amount: u64,     // Amount staked through the session so far
+    pub expires_at: i64,        // Timestamp after which the session can no longer be used
+    pub allowed_actions: u8,    // Bitmask of the game actions the session may perform
+    pub rent_payer: Pubkey,     // Paid the session's rent; refunded when it is revoked
+    pub bump: u8,
+}
+
+impl SessionKey {
+    /// Returns true if the session has not yet expired.
+    pub fn is_live(&self, current_time: i64) -> bool {
+        current_time < self.expires_at
+    }
+
+    /// Returns true if the session may perform the given action.
+    pub fn is_action_allowed(&self, action: u8) -> bool {
+        self.allowed_actions & action == action
+    }
+
+    /// Validates whether the session can perform an action on the owner's behalf.
+    pub fn validate_action(&self, action: u8, current_time: i64) -> Result<()> {
+        require!(self.is_live(current_time), CustomError::SessionExpired);
+        require!(
+            self.is_action_allowed(action),
+            CustomError::SessionActionNotAllowed
+        );
+
+        Ok(())
+    }
+
+    /// Records a stake made through the session, enforcing its limit.
+    pub fn record_stake(&mut self, stake_mint: Pubkey, amount: u64) -> Result<()> {
+        require!(stake_mint == self.stake_mint, CustomError::InvalidTokenMint);
+
+        let staked_amount = self
+            .staked_amount
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        require!(
+            staked_amount <= self.max_stake,
+            CustomError::SessionStakeLimitExceeded
+        );
+
+        self.staked_amount = staked_amount;
+
+        Ok(())
+    }
+
+    /// Returns the signer seeds for the session PDA, which is the delegate on the owner's
+    /// stake token account.
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            b"session",
+            self.owner.as_ref(),
+            self.session_signer.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
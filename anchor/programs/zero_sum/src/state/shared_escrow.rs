@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::common::CustomError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct SharedEscrow {
+    pub stake_mint: Pubkey,  // Token mint held in escrow
+    pub vault: Pubkey,       // Token account holding the stakes of every shared-escrow game
+    pub total_escrowed: u64, // Sum of the escrow amounts of all games using the vault
+    pub bump: u8,
+}
+
+impl SharedEscrow {
+    /// Adds stakes moved into the vault to the escrowed total.
+    pub fn lock(&mut self, amount: u64) -> Result<()> {
+        self.total_escrowed = self
+            .total_escrowed
+            .checked_add(amount)
+            .ok_or(error!(CustomError::Overflow))?;
+
+        Ok(())
+    }
+
+    /// Removes stakes paid out of the vault from the escrowed total.
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.total_escrowed = self
+            .total_escrowed
+            .checked_sub(amount)
+            .ok_or(error!(CustomError::EscrowInvariantViolated))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+use std::ops::Range;
+
+use anchor_lang::prelude::*;
+
+use crate::common::{calculate_bps_share, CustomError, TournamentStatus, MAX_TOURNAMENT_PLAYERS};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Tournament {
+    pub tournament_id: u64,        // Unique identifier for the tournament
+    pub stake_mint: Pubkey,        // Token mint the entry fee is paid in
+    pub vault: Pubkey,             // Token account holding the pot
+    pub entry_fee: u64,            // Fee to register (in base units)
+    pub max_players: u8,           // Most players that can register
+    pub registration_ends_at: i64, // Timestamp registration closes
+    pub status: TournamentStatus,  // Status of the tournament
+    pub round: u8,                 // Current bracket round, starting from zero
+    pub round_started_at: i64,     // Timestamp the current round started
+    pub pot: u64,                  // Total entry fees collected (in base units)
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub payout_curve: Vec<u16>, // Share of the pot paid to each finishing rank, in basis points
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub players: Vec<Pubkey>, // Registered players; registration order sets the seeding
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub slots: Vec<Pubkey>, // Current round pairings, match `i` is slots `2i` and `2i + 1`
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub match_games: Vec<Pubkey>, // Game played for each match of the current round
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub match_winners: Vec<Pubkey>, // Winner of each match of the current round
+    #[max_len(MAX_TOURNAMENT_PLAYERS)]
+    pub finish_order: Vec<Pubkey>, // Players in the order they were knocked out, champion last
+    pub claimed_prizes: u32,       // Bitmap of finish order positions whose prize was claimed
+    pub bump: u8,
+}
+
+impl Tournament {
+    /// Registers a player, who is seeded after everyone already registered.
+    pub fn register(&mut self, player: Pubkey, current_time: i64) -> Result<()> {
+        require!(
+            self.status == TournamentStatus::Registration
+                && current_time < self.registration_ends_at,
+            CustomError::RegistrationClosed
+        );
+        require!(
+            self.players.len() < self.max_players as usize && !self.players.contains(&player),
+            CustomError::CannotRegister
+        );
+
+        self.players.push(player);
+
+        Ok(())
+    }
+
+    /// Calls off a tournament that is still open for registration. The admin can cancel
+    /// it at any time, anyone else only once registration closed with too few players
+    /// to start.
+    pub fn cancel(&mut self, by_admin: bool, current_time: i64) -> Result<()> {
+        require!(
+            self.status == TournamentStatus::Registration
+                && (by_admin
+                    || (current_time >= self.registration_ends_at && self.players.len() < 2)),
+            CustomError::TournamentNotCancellable
+        );
+
+        self.status = TournamentStatus::Cancelled;
+
+        Ok(())
+    }
+
+    /// Removes a player from a cancelled tournament, returning the entry fee to refund.
+    pub fn refund(&mut self, player: Pubkey) -> Result<u64> {
+        require!(
+            self.status == TournamentStatus::Cancelled,
+            CustomError::TournamentNotCancelled
+        );
+
+        let seed = self
+            .get_seed(player)
+            .ok_or(error!(CustomError::NotTournamentPlayer))?;
+
+        self.players.remove(seed);
+        self.pot -= self.entry_fee;
+
+        Ok(self.entry_fee)
+    }
+
+    /// Draws the first round of the bracket once registration has closed or filled up.
+    ///
+    /// The bracket is padded to a power of two with byes, which go to the top seeds so
+    /// that no match is between two byes.
+    pub fn start(&mut self, current_time: i64) -> Result<()> {
+        let player_count = self.players.len();
+
+        require!(
+            self.status == TournamentStatus::Registration
+                && player_count >= 2
+                && (current_time >= self.registration_ends_at
+                    || player_count == self.max_players as usize),
+            CustomError::TournamentNotReady
+        );
+
+        let bracket_size = player_count.next_power_of_two();
+        let match_count = bracket_size / 2;
+
+        self.slots = Vec::with_capacity(bracket_size);
+        self.match_games = vec![Pubkey::default(); match_count];
+        self.match_winners = vec![Pubkey::default(); match_count];
+
+        // Pair the best seeds with the worst, giving byes to the top seeds
+        for index in 0..match_count {
+            let home = self.players[index];
+            let away = self
+                .players
+                .get(bracket_size - 1 - index)
+                .copied()
+                .unwrap_or_default();
+
+            self.slots.push(home);
+            self.slots.push(away);
+
+            if away == Pubkey::default() {
+                self.match_winners[index] = home;
+            }
+        }
+
+        self.status = TournamentStatus::InProgress;
+        self.round = 0;
+        self.round_started_at = current_time;
+
+        Ok(())
+    }
+
+    /// Returns the home and away players of an undecided match in the current round.
+    pub fn get_match(&self, match_index: usize) -> Result<(Pubkey, Pubkey)> {
+        require!(
+            self.status == TournamentStatus::InProgress,
+            CustomError::TournamentNotInProgress
+        );
+        require!(
+            self.match_winners.get(match_index) == Some(&Pubkey::default()),
+            CustomError::InvalidTournamentGame
+        );
+
+        Ok((self.slots[2 * match_index], self.slots[2 * match_index + 1]))
+    }
+
+    /// Links the game created by the home player to a match, returning the away player
+    /// it is reserved for.
+    pub fn assign_match_game(
+        &mut self,
+        match_index: usize,
+        initiator: Pubkey,
+        game: Pubkey,
+    ) -> Result<Pubkey> {
+        let (home, away) = self.get_match(match_index)?;
+
+        require!(home == initiator, CustomError::NotTournamentPlayer);
+        require!(
+            self.match_games[match_index] == Pubkey::default(),
+            CustomError::InvalidTournamentGame
+        );
+
+        self.match_games[match_index] = game;
+
+        Ok(away)
+    }
+
+    /// Returns the player's seed, where a lower seed is ranked higher.
+    pub fn get_seed(&self, player: Pubkey) -> Option<usize> {
+        self.players.iter().position(|p| *p == player)
+    }
+
+    /// Records a match result, moving on to the next round once every match is decided.
+    /// Returns true if this result decided the tournament.
+    pub fn record_match_result(
+        &mut self,
+        match_index: usize,
+        winner: Pubkey,
+        loser: Pubkey,
+        current_time: i64,
+    ) -> bool {
+        self.match_winners[match_index] = winner;
+        self.finish_order.push(loser);
+
+        if self.match_winners.contains(&Pubkey::default()) {
+            return false;
+        }
+
+        if self.match_winners.len() == 1 {
+            self.finish_order.push(winner);
+            self.status = TournamentStatus::Complete;
+            return true;
+        }
+
+        let match_count = self.match_winners.len() / 2;
+
+        self.slots = std::mem::take(&mut self.match_winners);
+        self.match_games = vec![Pubkey::default(); match_count];
+        self.match_winners = vec![Pubkey::default(); match_count];
+        self.round += 1;
+        self.round_started_at = current_time;
+
+        false
+    }
+
+    /// Returns the finishing ranks shared by the player at the given finish order position,
+    /// where the champion is rank zero. Players knocked out in the same round tie, so
+    /// they share every rank between those who went further and those who went out
+    /// earlier.
+    pub fn get_ranks(&self, position: usize) -> Range<usize> {
+        let mut round_start = 0;
+        let mut remaining = self.players.len();
+        let mut next_remaining = remaining.next_power_of_two() / 2;
+
+        loop {
+            let knocked_out = remaining - next_remaining;
+
+            if position < round_start + knocked_out {
+                return next_remaining..remaining;
+            }
+
+            round_start += knocked_out;
+            remaining = next_remaining;
+            next_remaining /= 2;
+        }
+    }
+
+    /// Returns the prize for a range of tied ranks, an even split of their shares.
+    pub fn get_prize(&self, ranks: Range<usize>) -> Result<u64> {
+        let share_bps: u16 = self
+            .payout_curve
+            .iter()
+            .take(ranks.end)
+            .skip(ranks.start)
+            .sum();
+
+        Ok(calculate_bps_share(self.pot, share_bps)? / ranks.len() as u64)
+    }
+
+    /// Marks the player's prize as claimed and returns their best shared rank and prize.
+    /// The champion also takes whatever the other prizes leave of the pot, i.e. the shares
+    /// of ranks nobody reached and the rounding dust.
+    pub fn claim_prize(&mut self, player: Pubkey) -> Result<(usize, u64)> {
+        let position = self
+            .finish_order
+            .iter()
+            .position(|p| *p == player)
+            .ok_or(error!(CustomError::NotATournamentWinner))?;
+
+        require!(
+            self.claimed_prizes & (1 << position) == 0,
+            CustomError::PrizeAlreadyClaimed
+        );
+
+        let ranks = self.get_ranks(position);
+        let amount = if ranks.start == 0 {
+            let mut other_prizes: u64 = 0;
+            for other in 0..position {
+                other_prizes += self.get_prize(self.get_ranks(other))?;
+            }
+            self.pot - other_prizes
+        } else {
+            self.get_prize(ranks.clone())?
+        };
+
+        require!(amount > 0, CustomError::NotATournamentWinner);
+
+        self.claimed_prizes |= 1 << position;
+
+        Ok((ranks.start, amount))
+    }
+}
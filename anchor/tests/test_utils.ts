@@ -5,8 +5,19 @@
 import * as anchor from "@coral-xyz/anchor";
 import { EventParser, Program } from "@coral-xyz/anchor";
 import { ZeroSum } from "../target/types/zero_sum";
-import { Connection, Keypair, PublicKey } from "@solana/web3.js";
-import { getAccount } from "@solana/spl-token";
+import {
+  AccountMeta,
+  Connection,
+  Keypair,
+  PublicKey,
+  Signer,
+} from "@solana/web3.js";
+import {
+  getAccount,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 
 /**
  * Standard time to wait between transactions to allow for blockchain confirmation
@@ -109,6 +120,213 @@ export function findVaultPDA(
   );
 }
 
+/**
+ * Find a Player Profile Program Derived Address
+ * @param programId The program ID
+ * @param player The player's public key
+ * @param stakeMint The stake mint address
+ * @returns Address and bump
+ */
+export function findPlayerProfilePDA(
+  programId: PublicKey,
+  player: PublicKey,
+  stakeMint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("player_profile"), player.toBuffer(), stakeMint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Find a Player Balance Program Derived Address
+ * @param programId The program ID
+ * @param owner The balance owner's public key
+ * @param stakeMint The stake mint address
+ * @returns Address and bump
+ */
+export function findPlayerBalancePDA(
+  programId: PublicKey,
+  owner: PublicKey,
+  stakeMint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("player_balance"), owner.toBuffer(), stakeMint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Find a Session Key Program Derived Address
+ * @param programId The program ID
+ * @param owner The wallet the session acts for
+ * @param sessionSigner The session's ephemeral signer
+ * @returns Address and bump
+ */
+export function findSessionPDA(
+  programId: PublicKey,
+  owner: PublicKey,
+  sessionSigner: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("session"), owner.toBuffer(), sessionSigner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Find a Referral Program Derived Address
+ * @param programId The program ID
+ * @param player The referred player's public key
+ * @returns Address and bump
+ */
+export function findReferralPDA(
+  programId: PublicKey,
+  player: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("referral"), player.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Find a Season Program Derived Address
+ * @param programId The program ID
+ * @param stakeMint The stake mint address
+ * @param seasonId The season ID
+ * @returns Address and bump
+ */
+export function findSeasonPDA(
+  programId: PublicKey,
+  stakeMint: PublicKey,
+  seasonId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("season"),
+      stakeMint.toBuffer(),
+      seasonId.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+}
+
+/**
+ * Find the temporary account a payer's SOL payouts are unwrapped through
+ * @param programId The program ID
+ * @param payer The payer's public key
+ * @returns Address and bump
+ */
+export function findSolPayoutPDA(
+  programId: PublicKey,
+  payer: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("sol_payout"), payer.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Find a Tournament Program Derived Address
+ * @param programId The program ID
+ * @param tournamentId The tournament ID
+ * @returns Address and bump
+ */
+export function findTournamentPDA(
+  programId: PublicKey,
+  tournamentId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tournament"), tournamentId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * Find a market's Price Sources Program Derived Address
+ * @param programId The program ID
+ * @param chainlinkFeed The market's Chainlink feed address
+ * @returns Address and bump
+ */
+export function findPriceSourcesPDA(
+  programId: PublicKey,
+  chainlinkFeed: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("price_sources"), chainlinkFeed.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Find the position token mints of a game's two sides
+ * @param programId The program ID
+ * @param gameState The game state address
+ * @returns The initiator's and the challenger's position mint addresses
+ */
+export function findPositionMintPDAs(
+  programId: PublicKey,
+  gameState: PublicKey
+): [PublicKey, PublicKey] {
+  const [initiatorPositionMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("initiator_position"), gameState.toBuffer()],
+    programId
+  );
+  const [challengerPositionMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from("challenger_position"), gameState.toBuffer()],
+    programId
+  );
+  return [initiatorPositionMint, challengerPositionMint];
+}
+
+/**
+ * Get the Token-2022 account holding a position token for its holder
+ * @param positionMint The position mint address
+ * @param holder The holder's public key
+ * @returns The holder's associated position token account
+ */
+export function getPositionAccount(
+  positionMint: PublicKey,
+  holder: PublicKey
+): PublicKey {
+  return getAssociatedTokenAddressSync(
+    positionMint,
+    holder,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+}
+
+/**
+ * Find the protocol Config Program Derived Address
+ * @param programId The program ID
+ * @returns Address and bump
+ */
+export function findConfigPDA(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+}
+
+/**
+ * Find a Program Derived Address seeded by a label and a stake mint, such as the
+ * mint's allowlist entry, referral vault or shared escrow
+ * @param programId The program ID
+ * @param label The PDA's seed label, e.g. "allowed_mint"
+ * @param stakeMint The stake mint address
+ * @returns Address and bump
+ */
+export function findMintPDA(
+  programId: PublicKey,
+  label: string,
+  stakeMint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(label), stakeMint.toBuffer()],
+    programId
+  );
+}
+
 /**
  * Parses events from transaction logs
  * @param provider The Anchor provider
@@ -159,15 +377,73 @@ export function extractPriceFromEvents(events: any[]): number | null {
   return null;
 }
 
+/**
+ * Checks whether a failed transaction's error reports the given program error
+ * @param error The error thrown when sending the transaction
+ * @param errorCode The program error name, e.g. "CannotJoinOwnGame"
+ * @returns True if the error message or its logs mention the error
+ */
+export function hasErrorCode(error: any, errorCode: string): boolean {
+  const logs: string[] = error?.logs ?? error?.transactionLogs ?? [];
+  return (
+    `${error?.message ?? error}`.includes(errorCode) ||
+    logs.some((log) => log.includes(errorCode))
+  );
+}
+
+/**
+ * Removes repeated signers, e.g. a player who also pays for the transaction
+ * @param signers The transaction's signers
+ * @returns Each signer once
+ */
+function uniqueSigners(...signers: Signer[]): Signer[] {
+  return signers.filter(
+    (signer, index) =>
+      signers.findIndex((other) => other.publicKey.equals(signer.publicKey)) ===
+      index
+  );
+}
+
+/**
+ * Lists price source accounts as remaining accounts, in the market's configured order
+ * @param sourceAccounts The price source accounts
+ * @returns Read-only account metas
+ */
+function toRemainingAccounts(sourceAccounts: PublicKey[] = []): AccountMeta[] {
+  return sourceAccounts.map((pubkey) => ({
+    pubkey,
+    isSigner: false,
+    isWritable: false,
+  }));
+}
+
+/**
+ * Options for creating a game
+ */
+export interface CreateGameOptions {
+  startAt?: anchor.BN | null; // Scheduled time to capture the initial price
+  anchorPriceAtJoin?: boolean; // Take the initial price again when joined
+  timeoutPolicy?: any; // How the pot is split on timeout, defaults to a refund
+  increaseThresholdBps?: number | null; // Rise the Increase side needs to win
+  decreaseThresholdBps?: number | null; // Fall the Decrease side needs to win
+  winCondition?: any; // Whether the threshold must be sustained, defaults to instant
+  payer?: Keypair; // Pays the game's rent, defaults to the initiator
+  authority?: Keypair; // Session signer acting for the initiator
+  fromBalance?: boolean; // Fund the stake from the initiator's internal balance
+  sharedEscrow?: boolean; // Hold the stake in the mint's shared escrow vault
+  sourceAccounts?: PublicKey[]; // Accounts of the market's price sources
+}
+
 /**
  * Creates a game and returns relevant data
  * @param program The program instance
  * @param initiator The initiator keypair
  * @param initiatorTokenAccount The initiator's token account
- * @param usdcMint The USDC mint address
+ * @param stakeMint The stake mint address
  * @param chainlinkFeed The Chainlink feed address
  * @param chainlinkProgram The Chainlink program ID
  * @param prediction The price prediction
+ * @param options Optional game settings and funding
  * @returns Object with game data and signature
  */
 export async function createGame(
@@ -175,10 +451,11 @@ export async function createGame(
   provider: anchor.AnchorProvider,
   initiator: Keypair,
   initiatorTokenAccount: PublicKey,
-  usdcMint: PublicKey,
+  stakeMint: PublicKey,
   chainlinkFeed: string,
   chainlinkProgram: PublicKey,
-  prediction: any
+  prediction: any,
+  options: CreateGameOptions = {}
 ): Promise<{
   gameId: anchor.BN;
   signature: string;
@@ -186,21 +463,67 @@ export async function createGame(
 }> {
   // Create a unique game ID
   const gameId = new anchor.BN(Date.now());
+  const programId = program.programId;
+  const authority = options.authority ?? initiator;
+  const payer = options.payer ?? initiator;
+
+  const [initiatorBalance] = findPlayerBalancePDA(
+    programId,
+    initiator.publicKey,
+    stakeMint
+  );
 
   // Transaction to create game
   const tx = await program.methods
-    .createGame(gameId, prediction)
-    .accounts({
+    .createGame(
+      gameId,
+      prediction,
+      options.startAt ?? null,
+      options.anchorPriceAtJoin ?? false,
+      options.timeoutPolicy ?? { refund: {} },
+      options.increaseThresholdBps ?? null,
+      options.decreaseThresholdBps ?? null,
+      options.winCondition ?? { instant: {} }
+    )
+    .accountsPartial({
+      authority: authority.publicKey,
+      payer: payer.publicKey,
       initiator: initiator.publicKey,
-      initiatorTokenAccount,
-      usdcMint,
+      session: authority.publicKey.equals(initiator.publicKey)
+        ? null
+        : findSessionPDA(programId, initiator.publicKey, authority.publicKey)[0],
+      stakeMint,
+      initiatorTokenAccount: options.fromBalance ? null : initiatorTokenAccount,
+      initiatorBalance: options.fromBalance ? initiatorBalance : null,
+      balanceVault: options.fromBalance
+        ? findMintPDA(programId, "balance_vault", stakeMint)[0]
+        : null,
+      allowedMint: findMintPDA(programId, "allowed_mint", stakeMint)[0],
+      vault: options.sharedEscrow || options.fromBalance
+        ? null
+        : findVaultPDA(programId, initiator.publicKey, gameId)[0],
+      sharedEscrow: options.sharedEscrow
+        ? findMintPDA(programId, "shared_escrow", stakeMint)[0]
+        : null,
+      sharedEscrowVault: options.sharedEscrow
+        ? findMintPDA(programId, "shared_escrow_vault", stakeMint)[0]
+        : null,
       chainlinkFeed,
       chainlinkProgram,
+      priceSources: findPriceSourcesPDA(
+        programId,
+        new PublicKey(chainlinkFeed)
+      )[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
     })
-    .signers([initiator])
+    .remainingAccounts(toRemainingAccounts(options.sourceAccounts))
+    .signers(uniqueSigners(authority, payer))
     .transaction();
 
-  const signature = await provider.sendAndConfirm(tx, [initiator]);
+  const signature = await provider.sendAndConfirm(
+    tx,
+    uniqueSigners(authority, payer)
+  );
 
   // Parse events to get initial price
   const events = await parseEvents(program, provider, signature);
@@ -226,7 +549,7 @@ export async function fetchCurrentPrice(
   // Fetch current price
   const tx = await program.methods
     .fetchPriceFromChainlink()
-    .accounts({
+    .accountsPartial({
       chainlinkFeed,
       chainlinkProgram,
     })
@@ -239,6 +562,19 @@ export async function fetchCurrentPrice(
   return extractPriceFromEvents(events);
 }
 
+/**
+ * Options for joining a game
+ */
+export interface JoinGameOptions {
+  expectedRefreshNonce?: number; // Refresh nonce the join was priced at, defaults to the current one
+  expectedInitialPrice?: number | null; // Initial price the challenger expects
+  maxAdverseDriftBps?: number | null; // Most the price may have moved against the challenger
+  payer?: Keypair; // Pays for the position tokens, defaults to the challenger
+  authority?: Keypair; // Session signer acting for the challenger
+  fromBalance?: boolean; // Fund the stake from the challenger's internal balance
+  sourceAccounts?: PublicKey[]; // Accounts of the game's price sources
+}
+
 /**
  * Attempts to join a game and returns result
  * @param program The program instance
@@ -247,9 +583,10 @@ export async function fetchCurrentPrice(
  * @param initiator The initiator's public key
  * @param challenger The challenger keypair
  * @param challengerTokenAccount The challenger's token account
- * @param usdcMint The USDC mint address
+ * @param stakeMint The stake mint address
  * @param chainlinkFeed The Chainlink feed address
  * @param chainlinkProgram The Chainlink program ID
+ * @param options Optional slippage limits and funding
  * @returns Object with join result
  */
 export async function joinGame(
@@ -259,25 +596,78 @@ export async function joinGame(
   initiator: PublicKey,
   challenger: Keypair,
   challengerTokenAccount: PublicKey,
-  usdcMint: PublicKey,
+  stakeMint: PublicKey,
   chainlinkFeed: string,
-  chainlinkProgram: PublicKey
+  chainlinkProgram: PublicKey,
+  options: JoinGameOptions = {}
 ): Promise<{ success: boolean; error?: any; signature?: string }> {
   try {
+    const programId = program.programId;
+    const authority = options.authority ?? challenger;
+    const payer = options.payer ?? challenger;
+
+    const [gameStateAddress] = findGameStatePDA(programId, initiator, gameId);
+    const game = await getGameState(program, gameStateAddress);
+    const [initiatorPositionMint, challengerPositionMint] =
+      findPositionMintPDAs(programId, gameStateAddress);
+
     // Transaction to join game
     const tx = await program.methods
-      .joinGame(gameId, initiator)
-      .accounts({
+      .joinGame(
+        gameId,
+        initiator,
+        options.expectedRefreshNonce ?? game?.refreshNonce ?? 0,
+        options.expectedInitialPrice ?? null,
+        options.maxAdverseDriftBps ?? null
+      )
+      .accountsPartial({
+        authority: authority.publicKey,
+        payer: payer.publicKey,
         challenger: challenger.publicKey,
-        challengerTokenAccount,
-        usdcMint,
+        session: authority.publicKey.equals(challenger.publicKey)
+          ? null
+          : findSessionPDA(programId, challenger.publicKey, authority.publicKey)[0],
+        initiatorWallet: initiator,
+        stakeMint,
+        challengerTokenAccount: options.fromBalance
+          ? null
+          : challengerTokenAccount,
+        challengerBalance: options.fromBalance
+          ? findPlayerBalancePDA(programId, challenger.publicKey, stakeMint)[0]
+          : null,
+        vault: game?.vault ?? findVaultPDA(programId, initiator, gameId)[0],
+        sharedEscrow: game?.sharedEscrow
+          ? findMintPDA(programId, "shared_escrow", stakeMint)[0]
+          : null,
+        gameState: gameStateAddress,
+        initiatorPositionMint,
+        challengerPositionMint,
+        initiatorPositionAccount: getPositionAccount(
+          initiatorPositionMint,
+          initiator
+        ),
+        challengerPositionAccount: getPositionAccount(
+          challengerPositionMint,
+          challenger.publicKey
+        ),
+        initiatorProfile: findPlayerProfilePDA(programId, initiator, stakeMint)[0],
+        challengerProfile: findPlayerProfilePDA(
+          programId,
+          challenger.publicKey,
+          stakeMint
+        )[0],
         chainlinkFeed,
         chainlinkProgram,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
-      .signers([challenger])
+      .remainingAccounts(toRemainingAccounts(options.sourceAccounts))
+      .signers(uniqueSigners(authority, payer))
       .transaction();
 
-    const signature = await provider.sendAndConfirm(tx, [challenger]);
+    const signature = await provider.sendAndConfirm(
+      tx,
+      uniqueSigners(authority, payer)
+    );
     return { success: true, signature };
   } catch (error) {
     return { success: false, error };
@@ -291,7 +681,8 @@ export async function joinGame(
  * @param gameId The game ID
  * @param initiator The initiator keypair
  * @param initiatorTokenAccount The initiator's token account
- * @param usdcMint The USDC mint address
+ * @param stakeMint The stake mint address
+ * @param payer Pays for the initiator's token account if it was closed, defaults to the initiator
  * @returns Object with cancel result
  */
 export async function cancelGame(
@@ -300,27 +691,113 @@ export async function cancelGame(
   gameId: anchor.BN,
   initiator: Keypair,
   initiatorTokenAccount: PublicKey,
-  usdcMint: PublicKey
+  stakeMint: PublicKey,
+  payer: Keypair = initiator
 ): Promise<{ success: boolean; error?: any; signature?: string }> {
   try {
+    const [gameStateAddress] = findGameStatePDA(
+      program.programId,
+      initiator.publicKey,
+      gameId
+    );
+    const game = await getGameState(program, gameStateAddress);
+
     // Transaction to cancel game
     const tx = await program.methods
       .cancelGame(gameId)
-      .accounts({
+      .accountsPartial({
         initiator: initiator.publicKey,
+        payer: payer.publicKey,
+        solPayout: findSolPayoutPDA(program.programId, payer.publicKey)[0],
+        rentPayer: game?.rentPayer ?? payer.publicKey,
         initiatorTokenAccount,
-        usdcMint,
+        initiatorBalance: game?.balanceEscrow
+          ? findPlayerBalancePDA(
+              program.programId,
+              initiator.publicKey,
+              stakeMint
+            )[0]
+          : null,
+        stakeMint,
+        vault:
+          game?.vault ??
+          findVaultPDA(program.programId, initiator.publicKey, gameId)[0],
+        sharedEscrow: game?.sharedEscrow
+          ? findMintPDA(program.programId, "shared_escrow", stakeMint)[0]
+          : null,
+        gameState: gameStateAddress,
+        initiatorProfile: findPlayerProfilePDA(
+          program.programId,
+          initiator.publicKey,
+          stakeMint
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
-      .signers([initiator])
+      .signers(uniqueSigners(initiator, payer))
       .transaction();
 
-    const signature = await provider.sendAndConfirm(tx, [initiator]);
+    const signature = await provider.sendAndConfirm(
+      tx,
+      uniqueSigners(initiator, payer)
+    );
     return { success: true, signature };
   } catch (error) {
     return { success: false, error };
   }
 }
 
+/**
+ * Gets the accounts settling a game's escrow, player profiles, protocol fee, season and
+ * referrals, shared by close_game and draw_game
+ * @param program The program instance
+ * @param gameStateAddress The game state address
+ * @param game The game state
+ * @returns The settlement accounts
+ */
+async function getSettlementAccounts(
+  program: Program<ZeroSum>,
+  gameStateAddress: PublicKey,
+  game: any
+) {
+  const programId = program.programId;
+  const stakeMint: PublicKey = game.stakeMint;
+  const challenger: PublicKey = game.challenger ?? PublicKey.default;
+
+  const [config] = findConfigPDA(programId);
+  const { treasury } = await program.account.config.fetch(config);
+
+  // The mint's current season must be passed whenever it has one
+  const [allowedMint] = findMintPDA(programId, "allowed_mint", stakeMint);
+  const { season } = await program.account.allowedMint.fetch(allowedMint);
+  const seasonVault = season
+    ? (await program.account.season.fetch(season)).vault
+    : null;
+
+  return {
+    rentPayer: game.rentPayer,
+    joinRentPayer: game.joinRentPayer,
+    stakeMint,
+    vault: game.vault,
+    sharedEscrow: game.sharedEscrow
+      ? findMintPDA(programId, "shared_escrow", stakeMint)[0]
+      : null,
+    housePool: game.housePool ?? null,
+    gameState: gameStateAddress,
+    initiatorProfile: findPlayerProfilePDA(programId, game.initiator, stakeMint)[0],
+    challengerProfile: findPlayerProfilePDA(programId, challenger, stakeMint)[0],
+    config,
+    treasury,
+    treasuryTokenAccount: getAssociatedTokenAddressSync(stakeMint, treasury, true),
+    allowedMint,
+    season,
+    seasonVault,
+    initiatorReferral: findReferralPDA(programId, game.initiator)[0],
+    challengerReferral: findReferralPDA(programId, challenger)[0],
+    referralVault: findMintPDA(programId, "referral_vault", stakeMint)[0],
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+}
+
 /**
  * Attempts to close a game and return result
  * @param program The program instance
@@ -329,9 +806,10 @@ export async function cancelGame(
  * @param initiator The initiator's public key
  * @param winner The winner's keypair
  * @param winnerTokenAccount The winner's token account
- * @param usdcMint The USDC mint address
+ * @param stakeMint The stake mint address
  * @param chainlinkFeed The Chainlink feed address
  * @param chainlinkProgram The Chainlink program ID
+ * @param roundIds Oracle rounds proving a sustained win, empty for instant wins
  * @returns Object with close result
  */
 export async function closeGame(
@@ -341,21 +819,59 @@ export async function closeGame(
   initiator: PublicKey,
   winner: Keypair,
   winnerTokenAccount: PublicKey,
-  usdcMint: PublicKey,
+  stakeMint: PublicKey,
   chainlinkFeed: string,
-  chainlinkProgram: PublicKey
+  chainlinkProgram: PublicKey,
+  roundIds: number[] = []
 ): Promise<{ success: boolean; error?: any; signature?: string }> {
   try {
+    const programId = program.programId;
+    const [gameStateAddress] = findGameStatePDA(programId, initiator, gameId);
+    const game = await program.account.gameState.fetch(gameStateAddress);
+
+    // The winner settles with their side's position token, burning the other side's
+    const winsInitiatorSide = game.initiatorHolder.equals(winner.publicKey);
+    const positionMint = winsInitiatorSide
+      ? game.initiatorPositionMint!
+      : game.challengerPositionMint!;
+    const loserPositionMint = winsInitiatorSide
+      ? game.challengerPositionMint!
+      : game.initiatorPositionMint!;
+    const loserHolder = winsInitiatorSide
+      ? game.challengerHolder!
+      : game.initiatorHolder;
+
     // Transaction to close game
     const tx = await program.methods
-      .closeGame(gameId, initiator)
-      .accounts({
+      .closeGame(gameId, initiator, roundIds)
+      .accountsPartial({
+        ...(await getSettlementAccounts(program, gameStateAddress, game)),
+        authority: winner.publicKey,
+        payer: winner.publicKey,
+        solPayout: findSolPayoutPDA(programId, winner.publicKey)[0],
         winner: winner.publicKey,
+        session: null,
         winnerTokenAccount,
-        usdcMint,
+        winnerBalance: game.balanceEscrow
+          ? findPlayerBalancePDA(programId, winner.publicKey, stakeMint)[0]
+          : null,
+        winnerPositionAccount: getPositionAccount(
+          positionMint,
+          winner.publicKey
+        ),
+        positionMint,
+        loserPositionMint,
+        loserPositionAccount: getPositionAccount(loserPositionMint, loserHolder),
         chainlinkFeed,
         chainlinkProgram,
       })
+      // Games keep the price sources they were created with. The tests only configure
+      // Chainlink sources, whose accounts are their feeds.
+      .remainingAccounts(
+        toRemainingAccounts(
+          game.priceSources?.sources.map((source: any) => source.chainlink.feed)
+        )
+      )
       .signers([winner])
       .transaction();
 
@@ -366,6 +882,155 @@ export async function closeGame(
   }
 }
 
+/**
+ * Attempts to settle a timed out game as a draw and return result
+ * @param program The program instance
+ * @param provider The Anchor provider
+ * @param gameId The game ID
+ * @param initiator The initiator's public key
+ * @param player The keypair of either side's holder
+ * @param chainlinkFeed The Chainlink feed address
+ * @param chainlinkProgram The Chainlink program ID
+ * @returns Object with draw result
+ */
+export async function drawGame(
+  program: Program<ZeroSum>,
+  provider: anchor.AnchorProvider,
+  gameId: anchor.BN,
+  initiator: PublicKey,
+  player: Keypair,
+  chainlinkFeed: string,
+  chainlinkProgram: PublicKey
+): Promise<{ success: boolean; error?: any; signature?: string }> {
+  try {
+    const programId = program.programId;
+    const [gameStateAddress] = findGameStatePDA(programId, initiator, gameId);
+    const game = await program.account.gameState.fetch(gameStateAddress);
+    const challengerHolder = game.challengerHolder ?? PublicKey.default;
+
+    // Both sides are settled at once, wherever their position tokens are held
+    const tx = await program.methods
+      .drawGame(gameId, initiator)
+      .accountsPartial({
+        ...(await getSettlementAccounts(program, gameStateAddress, game)),
+        authority: player.publicKey,
+        payer: player.publicKey,
+        solPayout: findSolPayoutPDA(programId, player.publicKey)[0],
+        player: player.publicKey,
+        session: null,
+        initiatorHolder: game.initiatorHolder,
+        initiatorTokenAccount: getAssociatedTokenAddressSync(
+          game.stakeMint,
+          game.initiatorHolder,
+          true
+        ),
+        initiatorPositionAccount: getPositionAccount(
+          game.initiatorPositionMint!,
+          game.initiatorHolder
+        ),
+        initiatorBalance: game.balanceEscrow
+          ? findPlayerBalancePDA(programId, game.initiatorHolder, game.stakeMint)[0]
+          : null,
+        initiatorPositionMint: game.initiatorPositionMint!,
+        challengerHolder,
+        challengerTokenAccount: getAssociatedTokenAddressSync(
+          game.stakeMint,
+          challengerHolder,
+          true
+        ),
+        challengerPositionAccount: getPositionAccount(
+          game.challengerPositionMint!,
+          challengerHolder
+        ),
+        challengerBalance: game.balanceEscrow
+          ? findPlayerBalancePDA(programId, challengerHolder, game.stakeMint)[0]
+          : null,
+        challengerPositionMint: game.challengerPositionMint!,
+        chainlinkFeed,
+        chainlinkProgram,
+      })
+      // Pots split by price are priced from the game's price sources, as in closeGame
+      .remainingAccounts(
+        toRemainingAccounts(
+          game.priceSources?.sources.map((source: any) => source.chainlink.feed)
+        )
+      )
+      .signers([player])
+      .transaction();
+
+    const signature = await provider.sendAndConfirm(tx, [player]);
+    return { success: true, signature };
+  } catch (error) {
+    return { success: false, error };
+  }
+}
+
+/**
+ * Attempts to transfer a player's side of an active game and return result
+ * @param program The program instance
+ * @param provider The Anchor provider
+ * @param gameId The game ID
+ * @param initiator The initiator's public key
+ * @param holder The keypair of the side's current holder
+ * @param newHolder The keypair of the side's new holder
+ * @param price Agreed price the new holder pays, in the stake mint's base units
+ * @returns Object with transfer result
+ */
+export async function transferPosition(
+  program: Program<ZeroSum>,
+  provider: anchor.AnchorProvider,
+  gameId: anchor.BN,
+  initiator: PublicKey,
+  holder: Keypair,
+  newHolder: Keypair,
+  price: anchor.BN
+): Promise<{ success: boolean; error?: any; signature?: string }> {
+  try {
+    const [gameStateAddress] = findGameStatePDA(
+      program.programId,
+      initiator,
+      gameId
+    );
+    const game = await program.account.gameState.fetch(gameStateAddress);
+    const positionMint = game.initiatorHolder.equals(holder.publicKey)
+      ? game.initiatorPositionMint!
+      : game.challengerPositionMint!;
+
+    // Both holders sign, as the new holder pays the agreed price
+    const tx = await program.methods
+      .transferPosition(gameId, initiator, price)
+      .accountsPartial({
+        holder: holder.publicKey,
+        newHolder: newHolder.publicKey,
+        payer: holder.publicKey,
+        holderTokenAccount: getAssociatedTokenAddressSync(
+          game.stakeMint,
+          holder.publicKey
+        ),
+        newHolderTokenAccount: getAssociatedTokenAddressSync(
+          game.stakeMint,
+          newHolder.publicKey
+        ),
+        holderPositionAccount: getPositionAccount(positionMint, holder.publicKey),
+        newHolderPositionAccount: getPositionAccount(
+          positionMint,
+          newHolder.publicKey
+        ),
+        positionMint,
+        stakeMint: game.stakeMint,
+        gameState: gameStateAddress,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([holder, newHolder])
+      .transaction();
+
+    const signature = await provider.sendAndConfirm(tx, [holder, newHolder]);
+    return { success: true, signature };
+  } catch (error) {
+    return { success: false, error };
+  }
+}
+
 /**
  * Checks if a game exists and returns game state
  * @param program The program instance
@@ -6,10 +6,21 @@ import {
   Keypair,
   LAMPORTS_PER_SOL,
   Connection,
+  SystemProgram,
+  Transaction,
 } from "@solana/web3.js";
 import {
+  createAssociatedTokenAccountIdempotent,
+  closeAccount,
+  createMint,
   getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
   getOrCreateAssociatedTokenAccount,
+  NATIVE_MINT,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  transfer,
 } from "@solana/spl-token";
 import * as fs from "fs";
 import path from "path";
@@ -18,11 +29,30 @@ import {
   fetchCurrentPrice,
   findGameStatePDA,
   findVaultPDA,
+  findConfigPDA,
+  findMintPDA,
+  findPlayerProfilePDA,
+  findPlayerBalancePDA,
+  findSessionPDA,
+  findReferralPDA,
+  findSeasonPDA,
+  findSolPayoutPDA,
+  findTournamentPDA,
+  findPriceSourcesPDA,
+  findPositionMintPDAs,
+  getPositionAccount,
   cancelGame,
   joinGame,
   createGame,
   calculatePriceChange,
   closeGame,
+  drawGame,
+  hasErrorCode,
+  transferPosition,
+  getGameState,
+  parseEvents,
+  extractPriceFromEvents,
+  CreateGameOptions,
 } from "./test_utils";
 
 /**
@@ -35,6 +65,8 @@ const CONFIG = {
   DEFAULT_CHALLENGERS_COUNT: 1,
   DEFAULT_INCREASE_COUNT: 1,
   ENTRY_AMOUNT: 1000, // USDC amount per game (for info display only)
+  ENTRY_AMOUNT_BASE_UNITS: 1_000_000_000, // 1000 USDC, the allowlisted entry amount
+  SOL_ENTRY_AMOUNT_LAMPORTS: 10_000_000, // 0.01 SOL per native SOL game
 };
 
 /**
@@ -55,9 +87,19 @@ const CONSTANTS = {
   // Reference: https://docs.chain.link/data-feeds/price-feeds/addresses?network=solana
   CHAINLINK_FEED_ADDRESS: "669U43LNHx7LsVj95uYksnhXUfWKDsdzVqev3V4Jpw3P",
 
+  // Upgradeable loader owning the program's data account, whose upgrade authority
+  // initializes the config
+  BPF_LOADER_UPGRADEABLE_PROGRAM_ID: new PublicKey(
+    "BPFLoaderUpgradeab1e11111111111111111111111"
+  ),
+
   MAX_JOIN_PRICE_MOVEMENT: 1, // 1% max change for joining
 
   WIN_PRICE_THRESHOLD: 5, // 5% movement for win
+
+  SESSION_ACTION_JOIN_GAME: 1 << 1, // Session keys allowed to join games only
+
+  BPS_DENOMINATOR: 10_000, // 100% in basis points
 };
 
 // Price prediction type and constants
@@ -76,6 +118,27 @@ const Logger = {
   plain: (message: string) => console.log(message),
 };
 
+/**
+ * Expects a transaction to fail with the given program error
+ * @param transaction The pending transaction
+ * @param errorCode The program error name, e.g. "InvalidTokenMint"
+ */
+async function expectProgramError(
+  transaction: Promise<unknown>,
+  errorCode: string
+): Promise<void> {
+  let error: any = null;
+  try {
+    await transaction;
+  } catch (err) {
+    error = err;
+  }
+
+  expect(error).not.toBeNull();
+  expect(hasErrorCode(error, errorCode)).toBe(true);
+  console.log(`Transaction correctly failed with ${errorCode}`);
+}
+
 function loadKeypairFromFile(filePath: string): Keypair {
   try {
     const keypairData = JSON.parse(fs.readFileSync(filePath, "utf8"));
@@ -237,11 +300,187 @@ describe("zero_sum", () => {
   let initiator: Keypair;
   let challenger: Keypair;
 
+  // The provider wallet maintains the protocol settings if it is the config admin
+  const admin = (provider.wallet as anchor.Wallet).payer;
+  const [configAddress] = findConfigPDA(program.programId);
+  let isAdmin = false;
+
   // Variables for token setup
   let usdcMint: PublicKey;
   let initiatorTokenAccount: PublicKey;
   let challengerTokenAccount: PublicKey;
 
+  /**
+   * Allowlists a stake mint with the given entry amount, if the admin hasn't already
+   * @param stakeMint The stake mint address
+   * @param entryAmount Stake per player in the mint's base units
+   * @returns Whether the mint is accepted for new games
+   */
+  async function ensureAllowedMint(
+    stakeMint: PublicKey,
+    entryAmount: number
+  ): Promise<boolean> {
+    const [allowedMint] = findMintPDA(program.programId, "allowed_mint", stakeMint);
+    const allowedMintAccount = await program.account.allowedMint.fetchNullable(
+      allowedMint
+    );
+
+    if (allowedMintAccount?.enabled) {
+      return true;
+    }
+
+    if (!isAdmin) {
+      Logger.warn(`Stake mint ${stakeMint.toString()} is not allowlisted`);
+      return false;
+    }
+
+    await program.methods
+      .setAllowedMint(new anchor.BN(entryAmount), true)
+      .accountsPartial({
+        admin: admin.publicKey,
+        config: configAddress,
+        stakeMint,
+        allowedMint,
+        referralVault: findMintPDA(program.programId, "referral_vault", stakeMint)[0],
+        balanceVault: findMintPDA(program.programId, "balance_vault", stakeMint)[0],
+        sharedEscrow: findMintPDA(program.programId, "shared_escrow", stakeMint)[0],
+        sharedEscrowVault: findMintPDA(
+          program.programId,
+          "shared_escrow_vault",
+          stakeMint
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    Logger.success(`Allowlisted stake mint ${stakeMint.toString()}`);
+    return true;
+  }
+
+  /**
+   * Creates a USDC game for the initiator on the ETH/USD feed
+   * @param prediction The initiator's prediction
+   * @param options Optional game settings
+   * @returns The game ID, its state address and its initial price
+   */
+  async function createTestGame(
+    prediction: PricePrediction,
+    options: CreateGameOptions = {}
+  ): Promise<{
+    gameId: anchor.BN;
+    gameStateAddress: PublicKey;
+    initialPrice: number | null;
+  }> {
+    const { gameId, initialPrice } = await createGame(
+      program,
+      provider,
+      initiator,
+      initiatorTokenAccount,
+      usdcMint,
+      CONSTANTS.CHAINLINK_FEED_ADDRESS,
+      CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+      prediction,
+      options
+    );
+    const [gameStateAddress] = findGameStatePDA(
+      program.programId,
+      initiator.publicKey,
+      gameId
+    );
+
+    return { gameId, gameStateAddress, initialPrice };
+  }
+
+  /**
+   * Creates a USDC game and joins it as the challenger
+   * @param prediction The initiator's prediction
+   * @param options Optional game settings
+   * @returns The game ID, state address and join signature, or null if the price moved
+   * too far to join
+   */
+  async function createJoinedGame(
+    prediction: PricePrediction,
+    options: CreateGameOptions = {}
+  ): Promise<{
+    gameId: anchor.BN;
+    gameStateAddress: PublicKey;
+    joinSignature: string;
+  } | null> {
+    const { gameId, gameStateAddress } = await createTestGame(
+      prediction,
+      options
+    );
+
+    const joinResult = await joinGame(
+      program,
+      provider,
+      gameId,
+      initiator.publicKey,
+      challenger,
+      challengerTokenAccount,
+      usdcMint,
+      CONSTANTS.CHAINLINK_FEED_ADDRESS,
+      CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID
+    );
+
+    if (!joinResult.success) {
+      // The price can move past the join limit between creating and joining
+      if (hasErrorCode(joinResult.error, "ExcessivePriceVolatility")) {
+        console.log("Skipping test as we can't join the game");
+        return null;
+      }
+      throw joinResult.error;
+    }
+
+    return { gameId, gameStateAddress, joinSignature: joinResult.signature! };
+  }
+
+  /**
+   * Creates a new wallet with a USDC token account, both funded by the challenger
+   * @param lamports SOL to fund the wallet with, in lamports
+   * @param usdcAmount USDC to fund the wallet with, in base units
+   * @returns The wallet and its USDC token account
+   */
+  async function createFundedWallet(
+    lamports: number,
+    usdcAmount: number = 0
+  ): Promise<{ wallet: Keypair; tokenAccount: PublicKey }> {
+    const wallet = Keypair.generate();
+
+    if (lamports > 0) {
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: challenger.publicKey,
+            toPubkey: wallet.publicKey,
+            lamports,
+          })
+        ),
+        [challenger]
+      );
+    }
+
+    const tokenAccount = await createAssociatedTokenAccountIdempotent(
+      connection,
+      challenger,
+      usdcMint,
+      wallet.publicKey
+    );
+
+    if (usdcAmount > 0) {
+      await transfer(
+        connection,
+        challenger,
+        challengerTokenAccount,
+        tokenAccount,
+        challenger,
+        usdcAmount
+      );
+    }
+
+    return { wallet, tokenAccount };
+  }
+
   beforeAll(async () => {
     console.log("Test setup beginning...");
 
@@ -282,6 +521,39 @@ describe("zero_sum", () => {
       usdcMint
     );
 
+    // Configure the protocol with the provider wallet as admin on first use, which
+    // requires it to be the program's upgrade authority
+    Logger.info("\nChecking protocol config...");
+    if (!(await program.account.config.fetchNullable(configAddress))) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        CONSTANTS.BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+      );
+
+      await program.methods
+        .initializeConfig()
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: configAddress,
+          program: program.programId,
+          programData,
+        })
+        .rpc();
+    }
+
+    const config = await program.account.config.fetch(configAddress);
+    isAdmin = config.admin.equals(admin.publicKey);
+    Logger.info(`Provider wallet ${isAdmin ? "is" : "is not"} the config admin`);
+
+    // USDC must be allowlisted for any game to be created
+    const usdcAllowed = await ensureAllowedMint(
+      usdcMint,
+      CONFIG.ENTRY_AMOUNT_BASE_UNITS
+    );
+    if (!usdcAllowed) {
+      Logger.critical("USDC is not an accepted stake mint");
+    }
+
     console.log("Test setup complete!");
   }, 60000);
 
@@ -332,7 +604,6 @@ describe("zero_sum", () => {
 
   it("Creates a game successfully", async () => {
     try {
-      const gameId = new anchor.BN(Date.now());
       const prediction: PricePrediction = { increase: {} };
 
       // Get initial token balance
@@ -341,31 +612,18 @@ describe("zero_sum", () => {
       ).amount;
 
       // Execute create_game instruction
-      const tx = await program.methods
-        .createGame(gameId, prediction)
-        .accounts({
-          initiator: initiator.publicKey,
-          initiatorTokenAccount,
-          usdcMint,
-          chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
-          chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
-        })
-        .signers([initiator])
-        .rpc();
-
-      console.log("Game creation transaction signature:", tx);
+      const { gameId, gameStateAddress } = await createTestGame(prediction);
 
       // Verify the game state
-      const [gameState] = findGameStatePDA(
-        program.programId,
-        initiator.publicKey,
-        gameId
+      const gameStateAccount = await program.account.gameState.fetch(
+        gameStateAddress
       );
-
-      const gameStateAccount = await program.account.gameState.fetch(gameState);
       expect(gameStateAccount.initiator.toString()).toBe(
         initiator.publicKey.toString()
       );
+      expect(gameStateAccount.initiatorHolder.toString()).toBe(
+        initiator.publicKey.toString()
+      );
       expect(gameStateAccount.initiatorPrediction).toEqual(prediction);
       expect(gameStateAccount.entryAmount.toString()).toBe("1000000000"); // 1000 USDC
       expect(gameStateAccount.gameId.toString()).toBe(gameId.toString());
@@ -400,30 +658,51 @@ describe("zero_sum", () => {
     }
   }, 30000);
 
+  it("Rejects games staked in a mint that isn't allowlisted", async () => {
+    try {
+      // A freshly created mint can't have been allowlisted by the admin
+      const unlistedMint = await createMint(
+        connection,
+        initiator,
+        initiator.publicKey,
+        null,
+        6
+      );
+      const unlistedTokenAccount = await createAssociatedTokenAccountIdempotent(
+        connection,
+        initiator,
+        unlistedMint,
+        initiator.publicKey
+      );
+
+      await expectProgramError(
+        createGame(
+          program,
+          provider,
+          initiator,
+          unlistedTokenAccount,
+          unlistedMint,
+          CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          PredictionIncrease
+        ),
+        "AccountNotInitialized"
+      );
+    } catch (error: any) {
+      console.error("Error in unlisted mint test:", error);
+      throw error;
+    }
+  }, 30000);
+
   it("Allows initiator to withdraw from a game that exists", async () => {
     try {
       // Create a new game first
-      const gameId = new anchor.BN(Date.now());
       const prediction: PricePrediction = { decrease: {} };
-
-      await program.methods
-        .createGame(gameId, prediction)
-        .accounts({
-          initiator: initiator.publicKey,
-          initiatorTokenAccount,
-          usdcMint,
-          chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
-          chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
-        })
-        .signers([initiator])
-        .rpc();
+      const { gameId, gameStateAddress: gameState } = await createTestGame(
+        prediction
+      );
 
       // Verify game was created
-      const [gameState] = findGameStatePDA(
-        program.programId,
-        initiator.publicKey,
-        gameId
-      );
 
       let gameStateAccount = await program.account.gameState.fetch(gameState);
       expect(gameStateAccount.initiatorPrediction).toEqual(prediction);
@@ -447,10 +726,11 @@ describe("zero_sum", () => {
       expect(result.success).toBe(true);
       console.log("Withdrawal transaction signature:", result.signature);
 
-      // Verify game state after withdrawal
-      gameStateAccount = await program.account.gameState.fetch(gameState);
-      expect(gameStateAccount.closedAt).not.toBeNull();
-      expect(gameStateAccount.status).toHaveProperty("cancelled");
+      // The cancelled game and its vault are closed, refunding their rent
+      expect(await getGameState(program, gameState)).toBeNull();
+      expect(
+        await provider.connection.getAccountInfo(gameStateAccount.vault)
+      ).toBeNull();
 
       // Check that tokens were returned to initiator
       const afterBalance = (
@@ -507,20 +787,8 @@ describe("zero_sum", () => {
   it("Prevents initiator from joining their own game", async () => {
     try {
       // Create a new game
-      const gameId = new anchor.BN(Date.now());
       const prediction: PricePrediction = { increase: {} };
-
-      await program.methods
-        .createGame(gameId, prediction)
-        .accounts({
-          initiator: initiator.publicKey,
-          initiatorTokenAccount,
-          usdcMint,
-          chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
-          chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
-        })
-        .signers([initiator])
-        .rpc();
+      const { gameId } = await createTestGame(prediction);
 
       // Get initial token balance
       const initialBalance = (
@@ -647,6 +915,9 @@ describe("zero_sum", () => {
         expect(gameState.challenger?.toString()).toBe(
           challenger.publicKey.toString()
         );
+        expect(gameState.challengerHolder?.toString()).toBe(
+          challenger.publicKey.toString()
+        );
         expect(gameState.startedAt).not.toBeNull();
         expect(gameState.status).toHaveProperty("active");
 
@@ -892,11 +1163,6 @@ describe("zero_sum", () => {
       expect(joinResult.success).toBe(true);
       console.log("Challenger joined game successfully");
 
-      // Get initial token balance before closing
-      const initiatorInitialBalance = (
-        await getAccount(provider.connection, initiatorTokenAccount)
-      ).amount;
-
       // Fetch current price to see if threshold is met
       const priceBeforeClose = await fetchCurrentPrice(
         program,
@@ -943,6 +1209,11 @@ describe("zero_sum", () => {
         console.log("Expected winner: Challenger (price decreased)");
       }
 
+      // Get the winner's token balance before closing
+      const winnerInitialBalance = (
+        await getAccount(provider.connection, expectedWinnerAccount)
+      ).amount;
+
       // Try to close the game
       const closeResult = await closeGame(
         program,
@@ -964,37 +1235,27 @@ describe("zero_sum", () => {
           expectedWinner.publicKey.toString().slice(0, 8) + "..."
         );
 
-        // Verify game state
+        // The completed game is closed along with its position mints
         const [gameStateAddress] = findGameStatePDA(
           program.programId,
           initiator.publicKey,
           gameId
         );
-        const gameState = await program.account.gameState.fetch(
-          gameStateAddress
-        );
-        expect(gameState.status).toHaveProperty("complete");
-        expect(gameState.closedAt).not.toBeNull();
+        expect(await getGameState(program, gameStateAddress)).toBeNull();
 
-        // Verify winner received funds (2000 USDC)
+        // Verify winner received the 2000 USDC pot less the protocol fee
+        const { protocolFeeBps } = await program.account.config.fetch(
+          configAddress
+        );
+        const pot = BigInt(2 * CONFIG.ENTRY_AMOUNT_BASE_UNITS);
+        const protocolFee =
+          (pot * BigInt(protocolFeeBps)) / BigInt(CONSTANTS.BPS_DENOMINATOR);
         const winnerAfterBalance = (
           await getAccount(provider.connection, expectedWinnerAccount)
         ).amount;
-
-        if (expectedWinner.publicKey.equals(initiator.publicKey)) {
-          // If initiator won, they should have 2000 USDC more than initial
-          const expectedBalance = initiatorInitialBalance + BigInt(2000000000);
-          expect(winnerAfterBalance.toString()).toBe(
-            expectedBalance.toString()
-          );
-        } else {
-          // If challenger won, check they received funds
-          // We don't track challenger's initial balance here, so just verify they got something
-          const challengerPrevBalance = (
-            await getAccount(provider.connection, challengerTokenAccount)
-          ).amount;
-          expect(winnerAfterBalance).toBeGreaterThan(challengerPrevBalance);
-        }
+        expect(winnerAfterBalance.toString()).toBe(
+          (winnerInitialBalance + pot - protocolFee).toString()
+        );
       } else {
         // If threshold is not met, close should fail
         expect(closeResult.success).toBe(false);
@@ -1018,4 +1279,1673 @@ describe("zero_sum", () => {
       throw error;
     }
   }, 30000);
+
+  it("Mints a position token for each side when a game is joined", async () => {
+    try {
+      const joined = await createJoinedGame(PredictionIncrease);
+      if (!joined) {
+        return;
+      }
+
+      const game = await program.account.gameState.fetch(
+        joined.gameStateAddress
+      );
+      const [initiatorPositionMint, challengerPositionMint] =
+        findPositionMintPDAs(program.programId, joined.gameStateAddress);
+      expect(game.initiatorPositionMint?.toString()).toBe(
+        initiatorPositionMint.toString()
+      );
+      expect(game.challengerPositionMint?.toString()).toBe(
+        challengerPositionMint.toString()
+      );
+
+      // Each side is a single Token-2022 token held by its player
+      const initiatorPosition = await getAccount(
+        provider.connection,
+        getPositionAccount(initiatorPositionMint, initiator.publicKey),
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      const challengerPosition = await getAccount(
+        provider.connection,
+        getPositionAccount(challengerPositionMint, challenger.publicKey),
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(initiatorPosition.amount.toString()).toBe("1");
+      expect(challengerPosition.amount.toString()).toBe("1");
+
+      const positionMint = await getMint(
+        provider.connection,
+        initiatorPositionMint,
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(positionMint.supply.toString()).toBe("1");
+      expect(positionMint.decimals).toBe(0);
+    } catch (error: any) {
+      console.error("Error in position token test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Transfers a side of an active game to a new holder", async () => {
+    try {
+      const joined = await createJoinedGame(PredictionIncrease);
+      if (!joined) {
+        return;
+      }
+
+      const { gameId, gameStateAddress } = joined;
+      const price = new anchor.BN(1_000_000); // 1 USDC
+      // The buyer pays rent for their position token account
+      const { wallet: buyer, tokenAccount: buyerTokenAccount } =
+        await createFundedWallet(0.01 * LAMPORTS_PER_SOL, price.toNumber());
+
+      // A side can't be handed to the other player
+      const selfTransfer = await transferPosition(
+        program,
+        provider,
+        gameId,
+        initiator.publicKey,
+        challenger,
+        initiator,
+        price
+      );
+      expect(selfTransfer.success).toBe(false);
+      expect(hasErrorCode(selfTransfer.error, "InvalidPositionRecipient")).toBe(
+        true
+      );
+
+      const sellerInitialBalance = (
+        await getAccount(provider.connection, challengerTokenAccount)
+      ).amount;
+
+      const result = await transferPosition(
+        program,
+        provider,
+        gameId,
+        initiator.publicKey,
+        challenger,
+        buyer,
+        price
+      );
+      expect(result.success).toBe(true);
+
+      // The buyer holds the challenger's side and paid the agreed price for it
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.challenger?.toString()).toBe(challenger.publicKey.toString());
+      expect(game.challengerHolder?.toString()).toBe(
+        buyer.publicKey.toString()
+      );
+
+      const buyerPosition = await getAccount(
+        provider.connection,
+        getPositionAccount(game.challengerPositionMint!, buyer.publicKey),
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(buyerPosition.amount.toString()).toBe("1");
+      const sellerPosition = await getAccount(
+        provider.connection,
+        getPositionAccount(game.challengerPositionMint!, challenger.publicKey),
+        "confirmed",
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(sellerPosition.amount.toString()).toBe("0");
+
+      const sellerAfterBalance = (
+        await getAccount(provider.connection, challengerTokenAccount)
+      ).amount;
+      expect(sellerAfterBalance.toString()).toBe(
+        (sellerInitialBalance + BigInt(price.toString())).toString()
+      );
+      expect(
+        (await getAccount(provider.connection, buyerTokenAccount)).amount
+      ).toBe(BigInt(0));
+    } catch (error: any) {
+      console.error("Error in transfer position test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Stakes native SOL, wrapping it from the wallet", async () => {
+    try {
+      if (!(await ensureAllowedMint(NATIVE_MINT, CONFIG.SOL_ENTRY_AMOUNT_LAMPORTS))) {
+        console.log("Skipping test as native SOL is not an accepted stake mint");
+        return;
+      }
+
+      const wrappedSolAccount = await createAssociatedTokenAccountIdempotent(
+        connection,
+        initiator,
+        NATIVE_MINT,
+        initiator.publicKey
+      );
+      const [allowedMint] = findMintPDA(
+        program.programId,
+        "allowed_mint",
+        NATIVE_MINT
+      );
+      const { entryAmount } = await program.account.allowedMint.fetch(
+        allowedMint
+      );
+
+      const initialLamports = await provider.connection.getBalance(
+        initiator.publicKey
+      );
+
+      const { gameId } = await createGame(
+        program,
+        provider,
+        initiator,
+        wrappedSolAccount,
+        NATIVE_MINT,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionIncrease
+      );
+
+      const [gameStateAddress] = findGameStatePDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.stakeMint.toString()).toBe(NATIVE_MINT.toString());
+      expect(game.entryAmount.toString()).toBe(entryAmount.toString());
+
+      // The stake is wrapped straight into the game's vault
+      const [vault] = findVaultPDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+      const vaultBalance = (await getAccount(provider.connection, vault))
+        .amount;
+      expect(vaultBalance.toString()).toBe(entryAmount.toString());
+
+      const afterCreateLamports = await provider.connection.getBalance(
+        initiator.publicKey
+      );
+      expect(initialLamports - afterCreateLamports).toBeGreaterThanOrEqual(
+        entryAmount.toNumber()
+      );
+
+      // Cancelling pays the stake back out as lamports
+      const result = await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        wrappedSolAccount,
+        NATIVE_MINT
+      );
+      expect(result.success).toBe(true);
+
+      const afterCancelLamports = await provider.connection.getBalance(
+        initiator.publicKey
+      );
+      expect(afterCancelLamports - afterCreateLamports).toBeGreaterThanOrEqual(
+        entryAmount.toNumber()
+      );
+      expect(
+        (await getAccount(provider.connection, wrappedSolAccount)).amount
+      ).toBe(BigInt(0));
+    } catch (error: any) {
+      console.error("Error in native SOL test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Recreates a closed token account when refunding a cancelled game", async () => {
+    try {
+      const { wallet: player, tokenAccount: playerTokenAccount } =
+        await createFundedWallet(
+          0.05 * LAMPORTS_PER_SOL,
+          CONFIG.ENTRY_AMOUNT_BASE_UNITS
+        );
+
+      const { gameId } = await createGame(
+        program,
+        provider,
+        player,
+        playerTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionDecrease
+      );
+
+      // The whole balance was staked, so the player can close their token account
+      await closeAccount(
+        connection,
+        player,
+        playerTokenAccount,
+        player.publicKey,
+        player
+      );
+      expect(
+        await provider.connection.getAccountInfo(playerTokenAccount)
+      ).toBeNull();
+
+      // Someone else pays to recreate it, and the refund still reaches the player
+      const result = await cancelGame(
+        program,
+        provider,
+        gameId,
+        player,
+        playerTokenAccount,
+        usdcMint,
+        challenger
+      );
+      expect(result.success).toBe(true);
+
+      const refundedBalance = (
+        await getAccount(provider.connection, playerTokenAccount)
+      ).amount;
+      expect(refundedBalance.toString()).toBe(
+        CONFIG.ENTRY_AMOUNT_BASE_UNITS.toString()
+      );
+
+      // Return the stake to the challenger that funded it
+      await transfer(
+        connection,
+        player,
+        playerTokenAccount,
+        challengerTokenAccount,
+        player,
+        CONFIG.ENTRY_AMOUNT_BASE_UNITS
+      );
+    } catch (error: any) {
+      console.error("Error in token account recreation test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Lets a session key join games for its owner within its allowance", async () => {
+    try {
+      const sessionSigner = Keypair.generate();
+      const [session] = findSessionPDA(
+        program.programId,
+        challenger.publicKey,
+        sessionSigner.publicKey
+      );
+      const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+      await program.methods
+        .createSession(
+          sessionSigner.publicKey,
+          new anchor.BN(CONFIG.ENTRY_AMOUNT_BASE_UNITS),
+          expiresAt,
+          CONSTANTS.SESSION_ACTION_JOIN_GAME
+        )
+        .accountsPartial({
+          owner: challenger.publicKey,
+          payer: challenger.publicKey,
+          stakeMint: usdcMint,
+          ownerTokenAccount: challengerTokenAccount,
+          session,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([challenger])
+        .rpc();
+
+      try {
+        // The session may only join, so it can't create games for its owner
+        await expectProgramError(
+          createGame(
+            program,
+            provider,
+            challenger,
+            challengerTokenAccount,
+            usdcMint,
+            CONSTANTS.CHAINLINK_FEED_ADDRESS,
+            CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+            PredictionIncrease,
+            { authority: sessionSigner, payer: admin }
+          ),
+          "SessionActionNotAllowed"
+        );
+
+        const { gameId } = await createGame(
+          program,
+          provider,
+          initiator,
+          initiatorTokenAccount,
+          usdcMint,
+          CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          PredictionIncrease
+        );
+        const [gameStateAddress] = findGameStatePDA(
+          program.programId,
+          initiator.publicKey,
+          gameId
+        );
+
+        // The session key signs the join and the provider pays for it, without the owner
+        const joinResult = await joinGame(
+          program,
+          provider,
+          gameId,
+          initiator.publicKey,
+          challenger,
+          challengerTokenAccount,
+          usdcMint,
+          CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          { authority: sessionSigner, payer: admin }
+        );
+
+        if (!joinResult.success) {
+          if (hasErrorCode(joinResult.error, "ExcessivePriceVolatility")) {
+            console.log("Skipping test as we can't join the game");
+            return;
+          }
+          throw joinResult.error;
+        }
+
+        const game = await program.account.gameState.fetch(gameStateAddress);
+        expect(game.challenger?.toString()).toBe(
+          challenger.publicKey.toString()
+        );
+
+        const sessionAccount = await program.account.sessionKey.fetch(session);
+        expect(sessionAccount.stakedAmount.toString()).toBe(
+          CONFIG.ENTRY_AMOUNT_BASE_UNITS.toString()
+        );
+      } finally {
+        await program.methods
+          .revokeSession()
+          .accountsPartial({
+            owner: challenger.publicKey,
+            solPayout: findSolPayoutPDA(program.programId, challenger.publicKey)[0],
+            rentPayer: challenger.publicKey,
+            stakeMint: usdcMint,
+            ownerTokenAccount: challengerTokenAccount,
+            session,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([challenger])
+          .rpc();
+      }
+
+      expect(await program.account.sessionKey.fetchNullable(session)).toBeNull();
+    } catch (error: any) {
+      console.error("Error in session key test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Snapshots both players' ratings when a game starts", async () => {
+    try {
+      const joined = await createJoinedGame(PredictionDecrease);
+      if (!joined) {
+        return;
+      }
+
+      const game = await program.account.gameState.fetch(
+        joined.gameStateAddress
+      );
+      const initiatorProfile = await program.account.playerProfile.fetch(
+        findPlayerProfilePDA(program.programId, initiator.publicKey, usdcMint)[0]
+      );
+      const challengerProfile = await program.account.playerProfile.fetch(
+        findPlayerProfilePDA(program.programId, challenger.publicKey, usdcMint)[0]
+      );
+
+      // Ratings only move when a game settles, so the snapshots match the profiles
+      expect(game.initiatorRating).toBe(initiatorProfile.rating);
+      expect(game.challengerRating).toBe(challengerProfile.rating);
+      expect(game.initiatorRating).toBeGreaterThan(0);
+    } catch (error: any) {
+      console.error("Error in rating snapshot test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Refunds a sponsor's rent and records the cancellation", async () => {
+    try {
+      // The challenger sponsors the rent of the initiator's game
+      const { gameId } = await createGame(
+        program,
+        provider,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionIncrease,
+        { payer: challenger }
+      );
+      const [gameStateAddress] = findGameStatePDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.rentPayer.toString()).toBe(challenger.publicKey.toString());
+
+      const [initiatorProfile] = findPlayerProfilePDA(
+        program.programId,
+        initiator.publicKey,
+        usdcMint
+      );
+      const initialCancellations =
+        (await program.account.playerProfile.fetchNullable(initiatorProfile))
+          ?.cancellations ?? new anchor.BN(0);
+      const sponsorInitialLamports = await provider.connection.getBalance(
+        challenger.publicKey
+      );
+
+      const result = await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+      expect(result.success).toBe(true);
+
+      // The emptied vault is closed and its rent goes back to the sponsor, not the initiator
+      const [vault] = findVaultPDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+      expect(await provider.connection.getAccountInfo(vault)).toBeNull();
+
+      const sponsorAfterLamports = await provider.connection.getBalance(
+        challenger.publicKey
+      );
+      expect(sponsorAfterLamports).toBeGreaterThan(sponsorInitialLamports);
+
+      const profile = await program.account.playerProfile.fetch(
+        initiatorProfile
+      );
+      expect(profile.cancellations.toString()).toBe(
+        initialCancellations.addn(1).toString()
+      );
+    } catch (error: any) {
+      console.error("Error in sponsored rent test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Opens a season for the stake mint", async () => {
+    try {
+      if (!isAdmin) {
+        console.log("Skipping test as the provider wallet is not the admin");
+        return;
+      }
+
+      const [allowedMint] = findMintPDA(program.programId, "allowed_mint", usdcMint);
+      const { season: currentSeason } = await program.account.allowedMint.fetch(
+        allowedMint
+      );
+
+      // Seasons can't overlap, so the new one opens once the current one ends
+      const now = Math.floor(Date.now() / 1000);
+      const startAt = currentSeason
+        ? Math.max(
+            now,
+            (await program.account.season.fetch(currentSeason)).endAt.toNumber()
+          )
+        : now;
+      const endAt = startAt + 3600;
+
+      const createSeason = (seasonId: anchor.BN, start: number, end: number) => {
+        const [season] = findSeasonPDA(program.programId, usdcMint, seasonId);
+        const [seasonVault] = PublicKey.findProgramAddressSync(
+          [Buffer.from("season_vault"), season.toBuffer()],
+          program.programId
+        );
+
+        return program.methods
+          .createSeason(seasonId, new anchor.BN(start), new anchor.BN(end), [
+            6000, 3000, 1000,
+          ])
+          .accountsPartial({
+            admin: admin.publicKey,
+            config: configAddress,
+            stakeMint: usdcMint,
+            allowedMint,
+            currentSeason: currentSeason ?? null,
+            season,
+            seasonVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      };
+
+      // A season must end after it starts
+      await expectProgramError(
+        createSeason(new anchor.BN(Date.now()), startAt, startAt),
+        "InvalidSeason"
+      );
+
+      const seasonId = new anchor.BN(Date.now() + 1);
+      const [season] = findSeasonPDA(program.programId, usdcMint, seasonId);
+      await createSeason(seasonId, startAt, endAt);
+
+      const seasonAccount = await program.account.season.fetch(season);
+      expect(seasonAccount.endAt.toNumber()).toBe(endAt);
+      expect(seasonAccount.prizePool.toString()).toBe("0");
+      expect(
+        (await program.account.allowedMint.fetch(allowedMint)).season?.toString()
+      ).toBe(season.toString());
+
+      // Prizes can't be claimed until the season ends
+      await expectProgramError(
+        program.methods
+          .claimSeasonPrize(seasonId)
+          .accountsPartial({
+            player: initiator.publicKey,
+            payer: initiator.publicKey,
+            solPayout: findSolPayoutPDA(program.programId, initiator.publicKey)[0],
+            stakeMint: usdcMint,
+            playerTokenAccount: initiatorTokenAccount,
+            season,
+            seasonVault: seasonAccount.vault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([initiator])
+          .rpc(),
+        "SeasonNotEnded"
+      );
+    } catch (error: any) {
+      console.error("Error in season test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Registers a player's referrer once", async () => {
+    try {
+      const player = Keypair.generate();
+      const [referral] = findReferralPDA(program.programId, player.publicKey);
+
+      const registerReferrer = (referrer: PublicKey) =>
+        program.methods
+          .registerReferrer(referrer)
+          .accountsPartial({
+            player: player.publicKey,
+            payer: admin.publicKey,
+            referral,
+          })
+          .signers([player])
+          .rpc();
+
+      // Players can't refer themselves
+      await expectProgramError(
+        registerReferrer(player.publicKey),
+        "InvalidReferrer"
+      );
+
+      await registerReferrer(initiator.publicKey);
+
+      const referralAccount = await program.account.referral.fetch(referral);
+      expect(referralAccount.player.toString()).toBe(
+        player.publicKey.toString()
+      );
+      expect(referralAccount.referrer.toString()).toBe(
+        initiator.publicKey.toString()
+      );
+
+      // The referrer is fixed once registered
+      const secondRegistration = registerReferrer(challenger.publicKey);
+      await expect(secondRegistration).rejects.toThrow();
+    } catch (error: any) {
+      console.error("Error in referral test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Refunds every player of a cancelled tournament", async () => {
+    try {
+      if (!isAdmin) {
+        console.log("Skipping test as the provider wallet is not the admin");
+        return;
+      }
+
+      const entryFee = new anchor.BN(1_000_000); // 1 USDC
+      const registrationEndsAt = new anchor.BN(
+        Math.floor(Date.now() / 1000) + 3600
+      );
+
+      const createTournament = (tournamentId: anchor.BN, payoutCurve: number[]) => {
+        const [tournament] = findTournamentPDA(program.programId, tournamentId);
+        const [tournamentVault] = PublicKey.findProgramAddressSync(
+          [Buffer.from("tournament_vault"), tournament.toBuffer()],
+          program.programId
+        );
+
+        return program.methods
+          .createTournament(
+            tournamentId,
+            entryFee,
+            2,
+            registrationEndsAt,
+            payoutCurve
+          )
+          .accountsPartial({
+            admin: admin.publicKey,
+            config: configAddress,
+            stakeMint: usdcMint,
+            tournament,
+            tournamentVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      };
+
+      // The payout curve must pay out the whole pot
+      await expectProgramError(
+        createTournament(new anchor.BN(Date.now()), [5000]),
+        "InvalidTournament"
+      );
+
+      const tournamentId = new anchor.BN(Date.now() + 1);
+      const [tournament] = findTournamentPDA(program.programId, tournamentId);
+      await createTournament(tournamentId, [7000, 3000]);
+
+      const { vault: tournamentVault } =
+        await program.account.tournament.fetch(tournament);
+      const players: [Keypair, PublicKey][] = [
+        [initiator, initiatorTokenAccount],
+        [challenger, challengerTokenAccount],
+      ];
+
+      for (const [player, playerTokenAccount] of players) {
+        await program.methods
+          .registerForTournament(tournamentId)
+          .accountsPartial({
+            player: player.publicKey,
+            stakeMint: usdcMint,
+            playerTokenAccount,
+            tournament,
+            tournamentVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      let tournamentAccount = await program.account.tournament.fetch(tournament);
+      expect(tournamentAccount.players.length).toBe(2);
+      expect(tournamentAccount.pot.toString()).toBe(
+        entryFee.muln(2).toString()
+      );
+
+      // The admin can call the tournament off while registration is open
+      await program.methods
+        .cancelTournament(tournamentId)
+        .accountsPartial({
+          authority: admin.publicKey,
+          config: configAddress,
+          tournament,
+        })
+        .rpc();
+
+      tournamentAccount = await program.account.tournament.fetch(tournament);
+      expect(tournamentAccount.status).toHaveProperty("cancelled");
+
+      for (const [player, playerTokenAccount] of players) {
+        const initialBalance = (
+          await getAccount(provider.connection, playerTokenAccount)
+        ).amount;
+
+        await program.methods
+          .claimTournamentRefund(tournamentId)
+          .accountsPartial({
+            player: player.publicKey,
+            payer: player.publicKey,
+            solPayout: findSolPayoutPDA(program.programId, player.publicKey)[0],
+            stakeMint: usdcMint,
+            playerTokenAccount,
+            tournament,
+            tournamentVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([player])
+          .rpc();
+
+        const afterBalance = (
+          await getAccount(provider.connection, playerTokenAccount)
+        ).amount;
+        expect(afterBalance.toString()).toBe(
+          (initialBalance + BigInt(entryFee.toString())).toString()
+        );
+      }
+
+      tournamentAccount = await program.account.tournament.fetch(tournament);
+      expect(tournamentAccount.players.length).toBe(0);
+      expect(tournamentAccount.pot.toString()).toBe("0");
+    } catch (error: any) {
+      console.error("Error in tournament test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Funds a game's stake from the player's internal balance and refunds it there", async () => {
+    try {
+      const [initiatorBalance] = findPlayerBalancePDA(
+        program.programId,
+        initiator.publicKey,
+        usdcMint
+      );
+      const [balanceVault] = findMintPDA(
+        program.programId,
+        "balance_vault",
+        usdcMint
+      );
+
+      await program.methods
+        .depositBalance(new anchor.BN(CONFIG.ENTRY_AMOUNT_BASE_UNITS))
+        .accountsPartial({
+          owner: initiator.publicKey,
+          payer: initiator.publicKey,
+          stakeMint: usdcMint,
+          ownerTokenAccount: initiatorTokenAccount,
+          ownerBalance: initiatorBalance,
+          balanceVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([initiator])
+        .rpc();
+
+      const depositedAmount = (
+        await program.account.playerBalance.fetch(initiatorBalance)
+      ).amount;
+      const tokenBalance = (
+        await getAccount(provider.connection, initiatorTokenAccount)
+      ).amount;
+
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionIncrease,
+        { fromBalance: true }
+      );
+
+      // The stake comes out of the internal balance, not the token account
+      const balanceAfterCreate = (
+        await program.account.playerBalance.fetch(initiatorBalance)
+      ).amount;
+      expect(balanceAfterCreate.toString()).toBe(
+        depositedAmount.subn(CONFIG.ENTRY_AMOUNT_BASE_UNITS).toString()
+      );
+      expect(
+        (await getAccount(provider.connection, initiatorTokenAccount)).amount
+      ).toBe(tokenBalance);
+
+      // The stake stays in the balance vault rather than moving to a game vault
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.balanceEscrow).toBe(true);
+      expect(game.vault.equals(balanceVault)).toBe(true);
+      expect(game.escrowAmount.toString()).toBe(
+        CONFIG.ENTRY_AMOUNT_BASE_UNITS.toString()
+      );
+
+      // Cancelling refunds the stake to the internal balance
+      const result = await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+      expect(result.success).toBe(true);
+      expect(
+        (await program.account.playerBalance.fetch(initiatorBalance)).amount.toString()
+      ).toBe(depositedAmount.toString());
+      expect(
+        (await getAccount(provider.connection, initiatorTokenAccount)).amount
+      ).toBe(tokenBalance);
+    } catch (error: any) {
+      console.error("Error in internal balance test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Holds a game's stake in the mint's shared escrow", async () => {
+    try {
+      const [sharedEscrow] = findMintPDA(
+        program.programId,
+        "shared_escrow",
+        usdcMint
+      );
+      const [sharedEscrowVault] = findMintPDA(
+        program.programId,
+        "shared_escrow_vault",
+        usdcMint
+      );
+      const initialEscrowed = (
+        await program.account.sharedEscrow.fetch(sharedEscrow)
+      ).totalEscrowed;
+
+      const { gameId } = await createGame(
+        program,
+        provider,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionDecrease,
+        { sharedEscrow: true }
+      );
+      const [gameStateAddress] = findGameStatePDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.sharedEscrow).toBe(true);
+      expect(game.vault.toString()).toBe(sharedEscrowVault.toString());
+      expect(game.escrowAmount.toString()).toBe(
+        CONFIG.ENTRY_AMOUNT_BASE_UNITS.toString()
+      );
+
+      let escrowed = (await program.account.sharedEscrow.fetch(sharedEscrow))
+        .totalEscrowed;
+      expect(escrowed.toString()).toBe(
+        initialEscrowed.addn(CONFIG.ENTRY_AMOUNT_BASE_UNITS).toString()
+      );
+
+      const result = await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+      expect(result.success).toBe(true);
+
+      // The shared vault stays open for other games
+      escrowed = (await program.account.sharedEscrow.fetch(sharedEscrow))
+        .totalEscrowed;
+      expect(escrowed.toString()).toBe(initialEscrowed.toString());
+      expect(
+        await provider.connection.getAccountInfo(sharedEscrowVault)
+      ).not.toBeNull();
+    } catch (error: any) {
+      console.error("Error in shared escrow test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Lets the house pool take the other side of games anchored at join", async () => {
+    try {
+      if (!isAdmin) {
+        console.log("Skipping test as the provider wallet is not the admin");
+        return;
+      }
+
+      const [housePool] = findMintPDA(program.programId, "house_pool", usdcMint);
+      const [shareMint] = findMintPDA(
+        program.programId,
+        "house_pool_shares",
+        usdcMint
+      );
+      const housePoolVault = getAssociatedTokenAddressSync(
+        usdcMint,
+        housePool,
+        true
+      );
+
+      await program.methods
+        .setHousePool(
+          new anchor.BN(10 * CONFIG.ENTRY_AMOUNT_BASE_UNITS),
+          new anchor.BN(10 * CONFIG.ENTRY_AMOUNT_BASE_UNITS)
+        )
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: configAddress,
+          stakeMint: usdcMint,
+          housePool,
+          housePoolVault,
+          shareMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      // The challenger provides the liquidity the pool stakes
+      await program.methods
+        .depositLiquidity(new anchor.BN(CONFIG.ENTRY_AMOUNT_BASE_UNITS))
+        .accountsPartial({
+          provider: challenger.publicKey,
+          payer: challenger.publicKey,
+          stakeMint: usdcMint,
+          providerTokenAccount: challengerTokenAccount,
+          providerShareAccount: getAssociatedTokenAddressSync(
+            shareMint,
+            challenger.publicKey
+          ),
+          housePool,
+          housePoolVault,
+          shareMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([challenger])
+        .rpc();
+
+      const houseJoinGame = async (gameId: anchor.BN) => {
+        const [gameState] = findGameStatePDA(
+          program.programId,
+          initiator.publicKey,
+          gameId
+        );
+        const game = await program.account.gameState.fetch(gameState);
+        const [initiatorPositionMint, challengerPositionMint] =
+          findPositionMintPDAs(program.programId, gameState);
+
+        return program.methods
+          .houseJoinGame(gameId, initiator.publicKey, game.refreshNonce)
+          .accountsPartial({
+            payer: admin.publicKey,
+            initiatorWallet: initiator.publicKey,
+            stakeMint: usdcMint,
+            housePool,
+            housePoolVault,
+            vault: game.vault,
+            sharedEscrow: null,
+            gameState,
+            initiatorPositionMint,
+            challengerPositionMint,
+            initiatorPositionAccount: getPositionAccount(
+              initiatorPositionMint,
+              initiator.publicKey
+            ),
+            challengerPositionAccount: getPositionAccount(
+              challengerPositionMint,
+              housePool
+            ),
+            initiatorProfile: findPlayerProfilePDA(
+              program.programId,
+              initiator.publicKey,
+              usdcMint
+            )[0],
+            housePoolProfile: findPlayerProfilePDA(
+              program.programId,
+              housePool,
+              usdcMint
+            )[0],
+            chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+            chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+      };
+
+      // The house only joins games priced at the join, so nobody can pick a side late
+      const unanchored = await createTestGame(PredictionIncrease);
+      await expectProgramError(
+        houseJoinGame(unanchored.gameId),
+        "HouseJoinRequiresAnchoredPrice"
+      );
+      await cancelGame(
+        program,
+        provider,
+        unanchored.gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionIncrease,
+        { anchorPriceAtJoin: true }
+      );
+      await houseJoinGame(gameId);
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.status).toHaveProperty("active");
+      expect(game.housePool?.toString()).toBe(housePool.toString());
+      expect(game.challenger?.toString()).toBe(housePool.toString());
+
+      const housePoolAccount = await program.account.housePool.fetch(housePool);
+      expect(housePoolAccount.decreaseExposure.toNumber()).toBeGreaterThanOrEqual(
+        CONFIG.ENTRY_AMOUNT_BASE_UNITS
+      );
+    } catch (error: any) {
+      console.error("Error in house pool test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Matches two pending games on opposite sides", async () => {
+    try {
+      const { gameId } = await createGame(
+        program,
+        provider,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionIncrease
+      );
+      const { gameId: matchedGameId } = await createGame(
+        program,
+        provider,
+        challenger,
+        challengerTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionDecrease
+      );
+      const [gameStateAddress] = findGameStatePDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+      const [matchedGameStateAddress] = findGameStatePDA(
+        program.programId,
+        challenger.publicKey,
+        matchedGameId
+      );
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      const matchedGame = await program.account.gameState.fetch(
+        matchedGameStateAddress
+      );
+      const [initiatorPositionMint, challengerPositionMint] =
+        findPositionMintPDAs(program.programId, gameStateAddress);
+
+      await program.methods
+        .matchGames(
+          gameId,
+          initiator.publicKey,
+          matchedGameId,
+          challenger.publicKey,
+          game.refreshNonce,
+          matchedGame.refreshNonce
+        )
+        .accountsPartial({
+          payer: initiator.publicKey,
+          initiatorWallet: initiator.publicKey,
+          matchedInitiatorWallet: challenger.publicKey,
+          matchedRentPayer: matchedGame.rentPayer,
+          stakeMint: usdcMint,
+          vault: game.vault,
+          matchedVault: matchedGame.vault,
+          sharedEscrow: null,
+          gameState: gameStateAddress,
+          matchedGameState: matchedGameStateAddress,
+          initiatorPositionMint,
+          challengerPositionMint,
+          initiatorPositionAccount: getPositionAccount(
+            initiatorPositionMint,
+            initiator.publicKey
+          ),
+          challengerPositionAccount: getPositionAccount(
+            challengerPositionMint,
+            challenger.publicKey
+          ),
+          initiatorProfile: findPlayerProfilePDA(
+            program.programId,
+            initiator.publicKey,
+            usdcMint
+          )[0],
+          challengerProfile: findPlayerProfilePDA(
+            program.programId,
+            challenger.publicKey,
+            usdcMint
+          )[0],
+          chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([initiator])
+        .rpc();
+
+      // The matched game's initiator becomes the challenger and its game is closed
+      const matched = await program.account.gameState.fetch(gameStateAddress);
+      expect(matched.status).toHaveProperty("active");
+      expect(matched.challenger?.toString()).toBe(
+        challenger.publicKey.toString()
+      );
+      expect(matched.escrowAmount.toString()).toBe(
+        (2 * CONFIG.ENTRY_AMOUNT_BASE_UNITS).toString()
+      );
+      expect(await getGameState(program, matchedGameStateAddress)).toBeNull();
+    } catch (error: any) {
+      console.error("Error in match games test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Refuses to match a tournament game", async () => {
+    try {
+      if (!isAdmin) {
+        console.log("Skipping test as the provider wallet is not the admin");
+        return;
+      }
+
+      // A full two-player tournament can start straight away, with the first player
+      // registered at home in the only match
+      const tournamentId = new anchor.BN(Date.now());
+      const [tournament] = findTournamentPDA(program.programId, tournamentId);
+      const [tournamentVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("tournament_vault"), tournament.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .createTournament(
+          tournamentId,
+          new anchor.BN(1_000_000), // 1 USDC
+          2,
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+          [10000]
+        )
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: configAddress,
+          stakeMint: usdcMint,
+          tournament,
+          tournamentVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      for (const [player, playerTokenAccount] of [
+        [initiator, initiatorTokenAccount],
+        [challenger, challengerTokenAccount],
+      ] as [Keypair, PublicKey][]) {
+        await program.methods
+          .registerForTournament(tournamentId)
+          .accountsPartial({
+            player: player.publicKey,
+            stakeMint: usdcMint,
+            playerTokenAccount,
+            tournament,
+            tournamentVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([player])
+          .rpc();
+      }
+
+      await program.methods
+        .startTournament(tournamentId)
+        .accountsPartial({ tournament })
+        .rpc();
+
+      const gameId = new anchor.BN(Date.now());
+      const [gameStateAddress] = findGameStatePDA(
+        program.programId,
+        initiator.publicKey,
+        gameId
+      );
+      await program.methods
+        .createTournamentGame(tournamentId, 0, gameId, PredictionIncrease)
+        .accountsPartial({
+          initiator: initiator.publicKey,
+          payer: initiator.publicKey,
+          tournament,
+          stakeMint: usdcMint,
+          vault: findVaultPDA(program.programId, initiator.publicKey, gameId)[0],
+          gameState: gameStateAddress,
+          chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          priceSources: findPriceSourcesPDA(
+            program.programId,
+            new PublicKey(CONSTANTS.CHAINLINK_FEED_ADDRESS)
+          )[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([initiator])
+        .rpc();
+
+      // The away player's own game on the other side can't be merged into the match
+      const { gameId: matchedGameId } = await createGame(
+        program,
+        provider,
+        challenger,
+        challengerTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        PredictionDecrease
+      );
+      const [matchedGameStateAddress] = findGameStatePDA(
+        program.programId,
+        challenger.publicKey,
+        matchedGameId
+      );
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      const matchedGame = await program.account.gameState.fetch(
+        matchedGameStateAddress
+      );
+      const [initiatorPositionMint, challengerPositionMint] =
+        findPositionMintPDAs(program.programId, gameStateAddress);
+
+      await expectProgramError(
+        program.methods
+          .matchGames(
+            gameId,
+            initiator.publicKey,
+            matchedGameId,
+            challenger.publicKey,
+            game.refreshNonce,
+            matchedGame.refreshNonce
+          )
+          .accountsPartial({
+            payer: initiator.publicKey,
+            initiatorWallet: initiator.publicKey,
+            matchedInitiatorWallet: challenger.publicKey,
+            matchedRentPayer: matchedGame.rentPayer,
+            stakeMint: usdcMint,
+            vault: game.vault,
+            matchedVault: matchedGame.vault,
+            sharedEscrow: null,
+            gameState: gameStateAddress,
+            matchedGameState: matchedGameStateAddress,
+            initiatorPositionMint,
+            challengerPositionMint,
+            initiatorPositionAccount: getPositionAccount(
+              initiatorPositionMint,
+              initiator.publicKey
+            ),
+            challengerPositionAccount: getPositionAccount(
+              challengerPositionMint,
+              challenger.publicKey
+            ),
+            initiatorProfile: findPlayerProfilePDA(
+              program.programId,
+              initiator.publicKey,
+              usdcMint
+            )[0],
+            challengerProfile: findPlayerProfilePDA(
+              program.programId,
+              challenger.publicKey,
+              usdcMint
+            )[0],
+            chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+            chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([initiator])
+          .rpc(),
+        "CannotMatchTournamentGame"
+      );
+
+      // Refund the away player's game
+      const cancelResult = await cancelGame(
+        program,
+        provider,
+        matchedGameId,
+        challenger,
+        challengerTokenAccount,
+        usdcMint
+      );
+      expect(cancelResult.success).toBe(true);
+    } catch (error: any) {
+      console.error("Error in tournament match test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Captures a scheduled game's initial price once its start time is reached", async () => {
+    try {
+      const startAt = Math.floor(Date.now() / 1000) + 20;
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionIncrease,
+        { startAt: new anchor.BN(startAt) }
+      );
+
+      let game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.startAt?.toNumber()).toBe(startAt);
+
+      const startGame = () =>
+        program.methods
+          .startGame(gameId, initiator.publicKey)
+          .accountsPartial({
+            gameState: gameStateAddress,
+            chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+            chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          })
+          .rpc();
+
+      await expectProgramError(startGame(), "StartTimeNotReached");
+
+      console.log("Waiting for the scheduled start...");
+      await sleep(25000);
+      await startGame();
+
+      game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.startAt).toBeNull();
+      expect(game.initialPrice).toBeGreaterThan(0);
+
+      await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+    } catch (error: any) {
+      console.error("Error in scheduled start test:", error);
+      throw error;
+    }
+  }, 90000);
+
+  it("Re-anchors the initial price at join for games created that way", async () => {
+    try {
+      const joined = await createJoinedGame(PredictionIncrease, {
+        anchorPriceAtJoin: true,
+      });
+      if (!joined) {
+        return;
+      }
+
+      // The game starts from the price the challenger joined at
+      const events = await parseEvents(program, provider, joined.joinSignature);
+      const joinPrice = extractPriceFromEvents(events);
+      const game = await program.account.gameState.fetch(
+        joined.gameStateAddress
+      );
+      expect(game.anchorPriceAtJoin).toBe(true);
+      expect(joinPrice).not.toBeNull();
+      expect(game.initialPrice).toBe(joinPrice);
+    } catch (error: any) {
+      console.error("Error in anchored join test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Rejects a join priced at a different initial price than expected", async () => {
+    try {
+      const { gameId, initialPrice } = await createTestGame(PredictionIncrease);
+
+      const joinResult = await joinGame(
+        program,
+        provider,
+        gameId,
+        initiator.publicKey,
+        challenger,
+        challengerTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        { expectedInitialPrice: (initialPrice ?? 0) + 1 }
+      );
+      expect(joinResult.success).toBe(false);
+
+      // The price may also have moved too far to join at all
+      if (!hasErrorCode(joinResult.error, "ExcessivePriceVolatility")) {
+        expect(hasErrorCode(joinResult.error, "UnexpectedInitialPrice")).toBe(
+          true
+        );
+      }
+
+      await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+    } catch (error: any) {
+      console.error("Error in join slippage test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Rejects joins priced before the game was refreshed", async () => {
+    try {
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionDecrease
+      );
+
+      await program.methods
+        .refreshGame(gameId)
+        .accountsPartial({
+          initiator: initiator.publicKey,
+          gameState: gameStateAddress,
+          chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        })
+        .signers([initiator])
+        .rpc();
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.refreshNonce).toBe(1);
+
+      const joinResult = await joinGame(
+        program,
+        provider,
+        gameId,
+        initiator.publicKey,
+        challenger,
+        challengerTokenAccount,
+        usdcMint,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+        { expectedRefreshNonce: 0 }
+      );
+      expect(joinResult.success).toBe(false);
+      expect(hasErrorCode(joinResult.error, "UnexpectedRefreshNonce")).toBe(true);
+
+      await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+    } catch (error: any) {
+      console.error("Error in refresh nonce test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Keeps a game's timeout policy and settles it only after the timeout", async () => {
+    try {
+      const joined = await createJoinedGame(PredictionIncrease, {
+        timeoutPolicy: { proportional: {} },
+      });
+      if (!joined) {
+        return;
+      }
+
+      const game = await program.account.gameState.fetch(
+        joined.gameStateAddress
+      );
+      expect(game.timeoutPolicy).toEqual({ proportional: {} });
+
+      const drawResult = await drawGame(
+        program,
+        provider,
+        joined.gameId,
+        initiator.publicKey,
+        initiator,
+        CONSTANTS.CHAINLINK_FEED_ADDRESS,
+        CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID
+      );
+      expect(drawResult.success).toBe(false);
+      expect(hasErrorCode(drawResult.error, "GameTimeoutNotReached")).toBe(true);
+    } catch (error: any) {
+      console.error("Error in timeout policy test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Sets each side's win threshold beyond the join price tolerance", async () => {
+    try {
+      // A threshold within the join tolerance could be joined after it was already won
+      await expectProgramError(
+        createTestGame(PredictionIncrease, { increaseThresholdBps: 100 }),
+        "InvalidWinThreshold"
+      );
+
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionIncrease,
+        { increaseThresholdBps: 600, decreaseThresholdBps: 700 }
+      );
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.increaseThresholdBps).toBe(600);
+      expect(game.decreaseThresholdBps).toBe(700);
+
+      await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+    } catch (error: any) {
+      console.error("Error in win threshold test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Validates sustained win conditions", async () => {
+    try {
+      await expectProgramError(
+        createTestGame(PredictionIncrease, {
+          winCondition: { consecutiveRounds: { rounds: 1 } },
+        }),
+        "InvalidWinCondition"
+      );
+
+      // The averaging window must fit within the game's timeout
+      await expectProgramError(
+        createTestGame(PredictionIncrease, {
+          winCondition: { timeWeighted: { windowSeconds: 1800 } },
+        }),
+        "InvalidWinCondition"
+      );
+
+      const winCondition = { timeWeighted: { windowSeconds: 600 } };
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionIncrease,
+        { winCondition }
+      );
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.winCondition).toEqual(winCondition);
+
+      await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+    } catch (error: any) {
+      console.error("Error in win condition test:", error);
+      throw error;
+    }
+  }, 30000);
+
+  it("Snapshots a market's price sources into new games", async () => {
+    try {
+      if (!isAdmin) {
+        console.log("Skipping test as the provider wallet is not the admin");
+        return;
+      }
+
+      const chainlinkFeed = new PublicKey(CONSTANTS.CHAINLINK_FEED_ADDRESS);
+      const [priceSources] = findPriceSourcesPDA(program.programId, chainlinkFeed);
+
+      await program.methods
+        .setPriceSources({ fallback: {} }, [
+          { chainlink: { feed: chainlinkFeed, maxStalenessSeconds: 3600 } },
+        ])
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: configAddress,
+          chainlinkFeed,
+          priceSources,
+        })
+        .rpc();
+
+      // Removing the sources only affects games created afterwards
+      const closePriceSources = () =>
+        program.methods
+          .closePriceSources()
+          .accountsPartial({
+            admin: admin.publicKey,
+            config: configAddress,
+            chainlinkFeed,
+            priceSources,
+          })
+          .rpc();
+
+      const { gameId, gameStateAddress } = await createTestGame(
+        PredictionIncrease,
+        { sourceAccounts: [chainlinkFeed] }
+      ).finally(closePriceSources);
+
+      expect(
+        await program.account.priceSources.fetchNullable(priceSources)
+      ).toBeNull();
+
+      const game = await program.account.gameState.fetch(gameStateAddress);
+      expect(game.priceSources?.policy).toEqual({ fallback: {} });
+      expect(game.priceSources?.sources).toHaveLength(1);
+
+      await cancelGame(
+        program,
+        provider,
+        gameId,
+        initiator,
+        initiatorTokenAccount,
+        usdcMint
+      );
+    } catch (error: any) {
+      console.error("Error in price sources test:", error);
+      throw error;
+    }
+  }, 60000);
+
+  it("Only accepts the Chainlink program and declares outages once feeds go stale", async () => {
+    try {
+      // A price read through any other program is rejected
+      await expectProgramError(
+        fetchCurrentPrice(
+          program,
+          provider,
+          CONSTANTS.CHAINLINK_FEED_ADDRESS,
+          SystemProgram.programId
+        ),
+        "InvalidPriceFeed"
+      );
+
+      const joined = await createJoinedGame(PredictionDecrease);
+      if (!joined) {
+        return;
+      }
+
+      // The feed is still updating, so the game must be settled normally
+      await expectProgramError(
+        program.methods
+          .declareOracleOutage(joined.gameId, initiator.publicKey)
+          .accountsPartial({
+            gameState: joined.gameStateAddress,
+            chainlinkFeed: CONSTANTS.CHAINLINK_FEED_ADDRESS,
+            chainlinkProgram: CONSTANTS.CHAINLINK_ONCHAIN_PROGRAM_ID,
+          })
+          .rpc(),
+        "OracleNotStale"
+      );
+    } catch (error: any) {
+      console.error("Error in oracle outage test:", error);
+      throw error;
+    }
+  }, 30000);
 });